@@ -0,0 +1,69 @@
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf}
+};
+
+const PUZZLES_DIR: &str = "src/puzzles";
+
+fn sorted_dirs(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| entries.filter_map(Result::ok).map(|e| e.path()).filter(|p| p.is_dir()).collect())
+        .unwrap_or_default();
+    dirs.sort();
+    dirs
+}
+
+fn name(path: &Path) -> String {
+    path.file_name().unwrap().to_string_lossy().into_owned()
+}
+
+// a day is any src/puzzles/day_NN directory with a mod.rs in it
+fn is_day(path: &Path) -> bool {
+    let name = name(path);
+    name.strip_prefix("day_").is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+        && path.join("mod.rs").is_file()
+}
+
+// the days! invocation of src/puzzles/mod.rs, so adding a day_NN directory
+// is all it takes to register one
+fn days(days: &[PathBuf]) -> String {
+    let mut out = String::new();
+    for day in days {
+        writeln!(out, "    {} => {:?},", name(day), day.join("mod.rs")).unwrap();
+    }
+    format!("days! {{\n{}}}\n", out)
+}
+
+// one test per src/puzzles/day_NN/examples/<name>/ directory, included by
+// tests/examples.rs
+fn example_tests(days: &[PathBuf]) -> String {
+    let mut tests = String::new();
+    for day in days {
+        for example in sorted_dirs(&day.join("examples")) {
+            let (day_name, example_name) = (name(day), name(&example));
+            let test_name: String = format!("{}_{}", day_name, example_name)
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect();
+
+            writeln!(tests, "#[test]").unwrap();
+            writeln!(tests, "fn {}() {{", test_name).unwrap();
+            writeln!(tests, "    check_example({:?}, {:?});", day_name, example_name).unwrap();
+            writeln!(tests, "}}\n").unwrap();
+        }
+    }
+    tests
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed={}", PUZZLES_DIR);
+
+    let puzzles_dir = PathBuf::from(env::var_os("CARGO_MANIFEST_DIR").unwrap()).join(PUZZLES_DIR);
+    let days_dirs: Vec<PathBuf> = sorted_dirs(&puzzles_dir).into_iter().filter(|d| is_day(d)).collect();
+
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    fs::write(out_dir.join("days.rs"), days(&days_dirs)).unwrap();
+    fs::write(out_dir.join("examples.rs"), example_tests(&days_dirs)).unwrap();
+}
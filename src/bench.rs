@@ -0,0 +1,190 @@
+use std::{
+    fmt::Write,
+    hint::black_box,
+    time::{Duration, Instant}
+};
+
+use crate::error::PuzzleError;
+use crate::history::Baseline;
+use crate::output::format_duration;
+use crate::puzzles::cancel::DEFAULT_TIMEOUT;
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BenchOptions {
+    pub iterations: usize,
+    pub warmup: usize,
+    // budget of every stage, warmup and measured runs included
+    pub timeout: Duration,
+    // compare medians against the bench history
+    pub compare: Option<Baseline>,
+    // relative slowdown reported as a regression, 0.1 is 10%
+    pub threshold: f64
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            iterations: 10,
+            warmup: 3,
+            timeout: DEFAULT_TIMEOUT,
+            compare: None,
+            threshold: 0.1
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+
+        let runs = sorted.len();
+        let median = if runs.is_multiple_of(2) {
+            (sorted[runs / 2 - 1] + sorted[runs / 2]) / 2
+        } else {
+            sorted[runs / 2]
+        };
+
+        let nanos: Vec<f64> = sorted.iter().map(|s| s.as_nanos() as f64).collect();
+        let mean = nanos.iter().sum::<f64>() / runs as f64;
+        let variance = nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / runs as f64;
+
+        Some(Self {
+            runs,
+            min: sorted[0],
+            median,
+            mean: Duration::from_nanos(mean.round() as u64),
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64)
+        })
+    }
+}
+
+pub struct StageStats {
+    pub stage: String,
+    pub stats: Result<Stats, String>
+}
+
+pub struct BenchResult {
+    pub puzzle_name: String,
+    pub stages: Vec<StageStats>
+}
+
+impl BenchResult {
+    pub fn new(puzzle_name: String) -> Self {
+        Self {
+            puzzle_name,
+            stages: vec![]
+        }
+    }
+
+    pub fn add_stage(&mut self, stage: &str, stats: Result<Stats, String>) {
+        self.stages.push(StageStats {
+            stage: stage.to_string(),
+            stats
+        });
+    }
+}
+
+// runs `f` warmup + iterations times, only the latter are measured
+pub fn measure<T, F>(options: &BenchOptions, mut f: F) -> Result<Stats, PuzzleError>
+where
+    F: FnMut() -> Result<T, PuzzleError>
+{
+    for _ in 0..options.warmup {
+        black_box(f()?);
+    }
+
+    let mut samples = Vec::with_capacity(options.iterations);
+    for _ in 0..options.iterations {
+        let now = Instant::now();
+        black_box(f()?);
+        samples.push(now.elapsed());
+    }
+
+    Stats::from_samples(&samples).ok_or_else(|| PuzzleError::solve("no iterations to measure"))
+}
+
+pub fn render(results: &[BenchResult]) -> String {
+    let mut out = String::new();
+
+    writeln!(
+        out, "{:<8} {:<10} {:>6} {:>12} {:>12} {:>12} {:>12}",
+        "day", "stage", "runs", "min", "median", "mean", "stddev"
+    ).unwrap();
+
+    for result in results {
+        for each_stage in &result.stages {
+            match &each_stage.stats {
+                Ok(stats) => writeln!(
+                    out, "{:<8} {:<10} {:>6} {:>12} {:>12} {:>12} {:>12}",
+                    result.puzzle_name,
+                    each_stage.stage,
+                    stats.runs,
+                    format_duration(stats.min),
+                    format_duration(stats.median),
+                    format_duration(stats.mean),
+                    format_duration(stats.stddev)
+                ).unwrap(),
+                Err(message) => writeln!(
+                    out, "{:<8} {:<10} error: {}",
+                    result.puzzle_name, each_stage.stage, message
+                ).unwrap()
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bench::*;
+
+    #[test]
+    fn test_stats_from_samples() {
+        // given
+        let _samples: Vec<Duration> = vec![4, 1, 3, 2].into_iter().map(Duration::from_millis).collect();
+
+        // when
+        let _res = Stats::from_samples(&_samples).unwrap();
+
+        // then
+        assert_eq!(_res.runs, 4);
+        assert_eq!(_res.min, Duration::from_millis(1));
+        assert_eq!(_res.median, Duration::from_micros(2500));
+        assert_eq!(_res.mean, Duration::from_micros(2500));
+        assert_eq!(_res.stddev, Duration::from_nanos(1_118_034));
+    }
+
+    #[test]
+    fn test_stats_from_no_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn test_measure_runs_warmup_and_iterations() {
+        // given
+        let _options = BenchOptions { iterations: 5, warmup: 2, ..BenchOptions::default() };
+        let mut _calls = 0;
+
+        // when
+        let _res = measure(&_options, || { _calls += 1; Ok(_calls) }).unwrap();
+
+        // then
+        assert_eq!(_res.runs, 5);
+        assert_eq!(_calls, 7);
+    }
+}
@@ -0,0 +1,705 @@
+use std::{path::PathBuf, time::Duration};
+
+use crate::bench::BenchOptions;
+use crate::history::{Baseline, HISTORY_VAR};
+use crate::output::Format;
+use crate::puzzles::{self, cancel::DEFAULT_TIMEOUT, input::InputSource, metadata::Tag, solution::Part};
+use crate::report::{ReportFormat, ReportOptions};
+
+pub const USAGE: &str = "\
+Usage:
+    aoc [run] [DAYS...] [--all] [--part <1|2>] [--input <FILE|->] [--format <FORMAT>] [--jobs <N>]
+                [--timeout <TIME>]
+    aoc bench [DAYS...] [--all] [--part <1|2>] [--input <FILE|->] [--iterations <N>] [--warmup <N>]
+                [--timeout <TIME>] [--compare[=previous|best]] [--threshold <PERCENT>]
+    aoc verify [DAYS...] [--all] [--part <1|2>] [--input <FILE>] [--jobs <N>] [--timeout <TIME>]
+    aoc compare [DAYS...] [--all] [--part <1|2>] [--input <DIR>] [--jobs <N>] [--timeout <TIME>]
+    aoc report [DAYS...] [--all] [--part <1|2>] [--input <FILE>] [--jobs <N>] [--timeout <TIME>]
+                [--output <FILE>] [--format <markdown|html>] [--mask]
+    aoc watch DAY [--part <1|2>] [--input <FILE>] [--timeout <TIME>]
+    aoc repl DAY
+    aoc lint [DAYS...] [--all] [--input <FILE|->]
+    aoc list [DAYS...] [--tag <TAG>] [--verbose]
+    aoc help
+
+Days:
+    7          single day
+    3..9       inclusive range of days
+    1,4,7      comma separated list of days
+
+Commands:
+    run        solve the puzzles and print the answers (default)
+    bench      run each part repeatedly and report timing statistics, timings
+               of the default inputs are appended to the bench history
+    verify     compare answers against answers.toml next to each input,
+               exits with 1 when an answer does not match
+    compare    solve every <name>.txt in <day>/inputs next to each input and
+               print the answers and timings side by side
+    report     solve and verify the puzzles and write a Markdown table or an
+               HTML page with titles, answers, verdicts and timing bars
+    watch      solve one day again whenever its input or examples change and
+               show how the answers changed
+    list       print the title and tags of each day, with --verbose also
+               a summary of the puzzle
+    lint       check inputs before solving them and point at every malformed
+               token, exits with 1 when a problem is found
+    repl       type input lines for one day and solve them interactively,
+               :help lists the commands of the day
+
+Options:
+    -a, --all          solve every available day (default when no days are given)
+    -p, --part <1|2>   solve only the given part
+    --tag <TAG>        only the days tagged TAG, one of bits, dynamic-programming,
+                       geometry, graph, grid, number-theory, parsing, search,
+                       sets or simulation
+    -i, --input <FILE> read input from FILE, or from stdin when FILE is '-'
+                       (single day only), compare reads the inputs in directory FILE
+    -f, --format <FORMAT>
+                       output format: text (default), json, csv or tsv,
+                       report takes markdown or html (default: from --output)
+    -o, --output <FILE>
+                       report only, write the report to FILE instead of stdout,
+                       FILE ending in .html gives an HTML page
+    --mask             report only, hide the answers
+    -v, --verbose      list only, print the summary of each day
+    -j, --jobs <N>     solve up to N days in parallel (default: number of CPUs),
+                       use 1 for sequential runs with trustworthy timings
+    -t, --timeout <TIME>
+                       give up on a part after TIME, e.g. 10s, 500ms or 2m,
+                       and report it as timed out (default 10s), bench gives
+                       TIME to every stage for all of its runs; a part given
+                       up on keeps running in the background until aoc exits
+                       unless its solver checks for cancellation
+    -n, --iterations <N>
+                       bench only, measured runs per part (default 10)
+    -w, --warmup <N>   bench only, unmeasured runs before measuring (default 3)
+    --compare[=previous|best]
+                       bench only, compare medians against the previous (default)
+                       or best run in the bench history, exits with 1 when a stage
+                       got slower than the threshold
+    --threshold <PERCENT>
+                       bench only, slowdown reported by --compare (default 10)
+    -h, --help         print this message
+
+Environment:
+    AOC_INPUT_DIR      directory with <day>/input.txt files, days missing there
+                       are read from src/puzzles/<day>/input.txt
+    AOC_BENCH_HISTORY  bench history file (default: bench_history.tsv)";
+
+#[derive(Debug, PartialEq)]
+pub struct RunArgs {
+    pub days: Vec<u8>,
+    pub parts: Vec<Part>,
+    pub input: InputSource,
+    pub format: Format,
+    // None means one job per CPU
+    pub jobs: Option<usize>,
+    pub timeout: Duration
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunArgs),
+    Bench(RunArgs, BenchOptions),
+    Verify(RunArgs),
+    Compare(RunArgs),
+    Report(RunArgs, ReportOptions),
+    Watch(RunArgs),
+    Lint(RunArgs),
+    Repl(u8),
+    // days and whether to print their summaries
+    List(Vec<u8>, bool),
+    Help
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+enum Mode {
+    Run,
+    Bench,
+    Verify,
+    Compare,
+    Report,
+    Watch,
+    Lint
+}
+
+fn parse_day(day: &str) -> Result<u8, String> {
+    let available = puzzles::available_days();
+
+    match day.trim().parse::<u8>() {
+        Ok(num) if available.contains(&num) => Ok(num),
+        _ => Err(format!(
+            "Invalid day '{}', expected a number between {} and {}",
+            day, available.first().unwrap(), available.last().unwrap()
+        ))
+    }
+}
+
+fn parse_days(spec: &str) -> Result<Vec<u8>, String> {
+    let mut days = vec![];
+
+    for each_spec in spec.split(',') {
+        match each_spec.find("..") {
+            Some(pos) => {
+                let start = parse_day(&each_spec[..pos])?;
+                let end = parse_day(&each_spec[pos + 2..])?;
+                if start > end {
+                    return Err(format!("Invalid range '{}', start is greater than end", each_spec));
+                }
+                days.extend(start..=end);
+            },
+            None => days.push(parse_day(each_spec)?)
+        }
+    }
+
+    Ok(days)
+}
+
+fn filter_by_tag(days: Vec<u8>, tag: Tag) -> Result<Vec<u8>, String> {
+    let tagged: Vec<u8> = days
+        .into_iter()
+        .filter(|day| puzzles::find_day(*day).is_some_and(|d| d.metadata.has_tag(tag)))
+        .collect();
+
+    if tagged.is_empty() {
+        return Err(format!("No selected day is tagged '{}'", tag));
+    }
+    Ok(tagged)
+}
+
+fn parse_count(value: &str, min: usize) -> Result<usize, String> {
+    match value.parse::<usize>() {
+        Ok(count) if count >= min => Ok(count),
+        _ => Err(format!("Invalid count '{}', expected a number of at least {}", value, min))
+    }
+}
+
+// "10" or "10%", returned as a fraction
+fn parse_threshold(value: &str) -> Result<f64, String> {
+    match value.trim_end_matches('%').parse::<f64>() {
+        Ok(percent) if percent.is_finite() && percent >= 0.0 => Ok(percent / 100.0),
+        _ => Err(format!("Invalid threshold '{}', expected a percentage like 10 or 2.5%", value))
+    }
+}
+
+// "10s", "500ms", "2m" or a plain number of seconds
+fn parse_timeout(value: &str) -> Result<Duration, String> {
+    let split = value.find(|c: char| !c.is_ascii_digit()).unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+
+    let timeout = match (amount.parse::<u64>(), unit) {
+        (Ok(amount), "ms") => Duration::from_millis(amount),
+        (Ok(amount), "s") | (Ok(amount), "") => Duration::from_secs(amount),
+        (Ok(amount), "m") => Duration::from_secs(amount * 60),
+        _ => return Err(format!("Invalid timeout '{}', expected e.g. 10s, 500ms or 2m", value))
+    };
+
+    if timeout.is_zero() {
+        return Err(format!("Invalid timeout '{}', expected more than zero", value));
+    }
+    Ok(timeout)
+}
+
+fn parse_run_args(args: &[String], mode: Mode) -> Result<Command, String> {
+    let mut days: Vec<u8> = vec![];
+    let mut all = false;
+    let mut parts: Vec<Part> = Part::ALL.to_vec();
+    let mut input = InputSource::Default;
+    let mut format = Format::Text;
+    let mut jobs = None;
+    let mut timeout = DEFAULT_TIMEOUT;
+    let mut options = BenchOptions::default();
+    let mut report = ReportOptions::default();
+    let mut report_format = None;
+    let mut tag = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        match arg {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-a" | "--all" => all = true,
+            "-p" | "--part" if mode != Mode::Lint => {
+                i += 1;
+                let value = args.get(i).ok_or(format!("Missing value for '{}'", arg))?;
+                parts = vec![value.parse::<Part>()?];
+            },
+            _ if mode != Mode::Lint && arg.starts_with("--part=") => {
+                parts = vec![arg["--part=".len()..].parse::<Part>()?];
+            },
+            "--tag" if mode != Mode::Watch => {
+                i += 1;
+                let value = args.get(i).ok_or(format!("Missing value for '{}'", arg))?;
+                tag = Some(value.parse::<Tag>()?);
+            },
+            _ if mode != Mode::Watch && arg.starts_with("--tag=") => {
+                tag = Some(arg["--tag=".len()..].parse::<Tag>()?);
+            },
+            "-i" | "--input" => {
+                i += 1;
+                let value = args.get(i).ok_or(format!("Missing value for '{}'", arg))?;
+                input = InputSource::from_arg(value);
+            },
+            _ if arg.starts_with("--input=") => {
+                input = InputSource::from_arg(&arg["--input=".len()..]);
+            },
+            "-f" | "--format" if mode == Mode::Run => {
+                i += 1;
+                let value = args.get(i).ok_or(format!("Missing value for '{}'", arg))?;
+                format = value.parse::<Format>()?;
+            },
+            _ if mode == Mode::Run && arg.starts_with("--format=") => {
+                format = arg["--format=".len()..].parse::<Format>()?;
+            },
+            "-f" | "--format" if mode == Mode::Report => {
+                i += 1;
+                let value = args.get(i).ok_or(format!("Missing value for '{}'", arg))?;
+                report_format = Some(value.parse::<ReportFormat>()?);
+            },
+            _ if mode == Mode::Report && arg.starts_with("--format=") => {
+                report_format = Some(arg["--format=".len()..].parse::<ReportFormat>()?);
+            },
+            "-o" | "--output" if mode == Mode::Report => {
+                i += 1;
+                let value = args.get(i).ok_or(format!("Missing value for '{}'", arg))?;
+                report.output = Some(PathBuf::from(value));
+            },
+            _ if mode == Mode::Report && arg.starts_with("--output=") => {
+                report.output = Some(PathBuf::from(&arg["--output=".len()..]));
+            },
+            "--mask" if mode == Mode::Report => report.mask = true,
+            "-j" | "--jobs" if !matches!(mode, Mode::Bench | Mode::Watch | Mode::Lint) => {
+                i += 1;
+                let value = args.get(i).ok_or(format!("Missing value for '{}'", arg))?;
+                jobs = Some(parse_count(value, 1)?);
+            },
+            _ if !matches!(mode, Mode::Bench | Mode::Watch | Mode::Lint) && arg.starts_with("--jobs=") => {
+                jobs = Some(parse_count(&arg["--jobs=".len()..], 1)?);
+            },
+            "-t" | "--timeout" if mode != Mode::Lint => {
+                i += 1;
+                let value = args.get(i).ok_or(format!("Missing value for '{}'", arg))?;
+                timeout = parse_timeout(value)?;
+            },
+            _ if mode != Mode::Lint && arg.starts_with("--timeout=") => {
+                timeout = parse_timeout(&arg["--timeout=".len()..])?;
+            },
+            "-n" | "--iterations" if mode == Mode::Bench => {
+                i += 1;
+                let value = args.get(i).ok_or(format!("Missing value for '{}'", arg))?;
+                options.iterations = parse_count(value, 1)?;
+            },
+            _ if mode == Mode::Bench && arg.starts_with("--iterations=") => {
+                options.iterations = parse_count(&arg["--iterations=".len()..], 1)?;
+            },
+            "-w" | "--warmup" if mode == Mode::Bench => {
+                i += 1;
+                let value = args.get(i).ok_or(format!("Missing value for '{}'", arg))?;
+                options.warmup = parse_count(value, 0)?;
+            },
+            _ if mode == Mode::Bench && arg.starts_with("--warmup=") => {
+                options.warmup = parse_count(&arg["--warmup=".len()..], 0)?;
+            },
+            "--compare" if mode == Mode::Bench => {
+                options.compare = Some(Baseline::Previous);
+            },
+            _ if mode == Mode::Bench && arg.starts_with("--compare=") => {
+                options.compare = Some(arg["--compare=".len()..].parse::<Baseline>()?);
+            },
+            "--threshold" if mode == Mode::Bench => {
+                i += 1;
+                let value = args.get(i).ok_or(format!("Missing value for '{}'", arg))?;
+                options.threshold = parse_threshold(value)?;
+            },
+            _ if mode == Mode::Bench && arg.starts_with("--threshold=") => {
+                options.threshold = parse_threshold(&arg["--threshold=".len()..])?;
+            },
+            _ if arg.starts_with('-') => return Err(format!("Unknown option '{}'", arg)),
+            _ => days.extend(parse_days(arg)?)
+        }
+        i += 1;
+    }
+
+    if mode == Mode::Watch && (all || days.len() != 1) {
+        return Err("'watch' needs exactly one day".to_string());
+    }
+
+    if all && !days.is_empty() {
+        return Err("'--all' cannot be combined with explicit days".to_string());
+    }
+
+    if days.is_empty() {
+        days = puzzles::available_days();
+    }
+
+    days.sort_unstable();
+    days.dedup();
+
+    if let Some(tag) = tag {
+        days = filter_by_tag(days, tag)?;
+    }
+
+    if input != InputSource::Default && days.len() != 1 {
+        return Err("'--input' requires exactly one day".to_string());
+    }
+
+    if matches!(mode, Mode::Verify | Mode::Report) && input == InputSource::Stdin {
+        return Err("'verify' and 'report' need an input file to find its answers".to_string());
+    }
+
+    if mode == Mode::Compare && input == InputSource::Stdin {
+        return Err("'compare' needs a directory of inputs".to_string());
+    }
+
+    if options.compare.is_some() && input != InputSource::Default {
+        return Err(format!("'--compare' only works with the default inputs kept in ${}", HISTORY_VAR));
+    }
+
+    if mode == Mode::Watch && input == InputSource::Stdin {
+        return Err("'watch' needs an input file to watch".to_string());
+    }
+
+    report.format = report_format.unwrap_or_else(|| report.output.as_deref().map_or(ReportFormat::Markdown, ReportFormat::from_path));
+
+    // bench applies it to every stage, kept with the other bench options
+    options.timeout = timeout;
+
+    let run_args = RunArgs { days, parts, input, format, jobs, timeout };
+    match mode {
+        Mode::Run => Ok(Command::Run(run_args)),
+        Mode::Bench => Ok(Command::Bench(run_args, options)),
+        Mode::Verify => Ok(Command::Verify(run_args)),
+        Mode::Compare => Ok(Command::Compare(run_args)),
+        Mode::Report => Ok(Command::Report(run_args, report)),
+        Mode::Watch => Ok(Command::Watch(run_args)),
+        Mode::Lint => Ok(Command::Lint(run_args))
+    }
+}
+
+fn parse_repl_args(args: &[String]) -> Result<Command, String> {
+    match args {
+        [arg] if arg == "-h" || arg == "--help" => Ok(Command::Help),
+        [day] => Ok(Command::Repl(parse_day(day)?)),
+        _ => Err("'repl' needs exactly one day".to_string())
+    }
+}
+
+fn parse_list_args(args: &[String]) -> Result<Command, String> {
+    let mut days: Vec<u8> = vec![];
+    let mut tag = None;
+    let mut verbose = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        match arg {
+            "-h" | "--help" => return Ok(Command::Help),
+            "-v" | "--verbose" => verbose = true,
+            "--tag" => {
+                i += 1;
+                let value = args.get(i).ok_or(format!("Missing value for '{}'", arg))?;
+                tag = Some(value.parse::<Tag>()?);
+            },
+            _ if arg.starts_with("--tag=") => {
+                tag = Some(arg["--tag=".len()..].parse::<Tag>()?);
+            },
+            _ if arg.starts_with('-') => return Err(format!("Unknown option '{}'", arg)),
+            _ => days.extend(parse_days(arg)?)
+        }
+        i += 1;
+    }
+
+    if days.is_empty() {
+        days = puzzles::available_days();
+    }
+    days.sort_unstable();
+    days.dedup();
+
+    if let Some(tag) = tag {
+        days = filter_by_tag(days, tag)?;
+    }
+    Ok(Command::List(days, verbose))
+}
+
+pub fn parse_args<I>(args: I) -> Result<Command, String>
+where
+    I: IntoIterator<Item = String>
+{
+    let args: Vec<String> = args.into_iter().collect();
+
+    match args.first().map(|a| a.as_str()) {
+        Some("help") => Ok(Command::Help),
+        Some("run") => parse_run_args(&args[1..], Mode::Run),
+        Some("bench") => parse_run_args(&args[1..], Mode::Bench),
+        Some("verify") => parse_run_args(&args[1..], Mode::Verify),
+        Some("compare") => parse_run_args(&args[1..], Mode::Compare),
+        Some("report") => parse_run_args(&args[1..], Mode::Report),
+        Some("watch") => parse_run_args(&args[1..], Mode::Watch),
+        Some("repl") => parse_repl_args(&args[1..]),
+        Some("list") => parse_list_args(&args[1..]),
+        Some("lint") => parse_run_args(&args[1..], Mode::Lint),
+        _ => parse_run_args(&args, Mode::Run)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::*;
+
+    fn args(input: &[&str]) -> Vec<String> {
+        input.iter().map(|a| a.to_string()).collect()
+    }
+
+    // what `aoc run DAYS` parses to, tests override the fields they set
+    fn run_args(days: Vec<u8>) -> RunArgs {
+        RunArgs { days, parts: Part::ALL.to_vec(), input: InputSource::Default, format: Format::Text, jobs: None, timeout: DEFAULT_TIMEOUT }
+    }
+
+    #[test]
+    fn test_parse_single_day_and_part() {
+        // given
+        let _input = args(&["run", "7", "--part", "2"]);
+
+        // when
+        let _res = parse_args(_input);
+
+        // then
+        assert_eq!(_res, Ok(Command::Run(RunArgs { parts: vec![Part::Two], ..run_args(vec![7]) })));
+    }
+
+    #[test]
+    fn test_parse_day_range_and_list() {
+        // given
+        let _input = args(&["run", "3..5", "9,1", "4"]);
+
+        // when
+        let _res = parse_args(_input);
+
+        // then
+        assert_eq!(_res, Ok(Command::Run(run_args(vec![1, 3, 4, 5, 9]))));
+    }
+
+    #[test]
+    fn test_parse_all() {
+        // given
+        let _input_1 = args(&["run", "--all", "-p", "1"]);
+        let _input_2 = args(&[]);
+
+        // when
+        let _res_1 = parse_args(_input_1);
+        let _res_2 = parse_args(_input_2);
+
+        // then
+        assert_eq!(_res_1, Ok(Command::Run(RunArgs { parts: vec![Part::One], ..run_args(puzzles::available_days()) })));
+        assert_eq!(_res_2, Ok(Command::Run(run_args(puzzles::available_days()))));
+    }
+
+    #[test]
+    fn test_parse_input() {
+        // given
+        let _input_1 = args(&["run", "8", "--input", "-"]);
+        let _input_2 = args(&["run", "8", "--input=my_input.txt"]);
+
+        // when
+        let _res_1 = parse_args(_input_1);
+        let _res_2 = parse_args(_input_2);
+
+        // then
+        assert_eq!(_res_1, Ok(Command::Run(RunArgs { input: InputSource::Stdin, ..run_args(vec![8]) })));
+        assert_eq!(_res_2, Ok(Command::Run(RunArgs { input: InputSource::from_arg("my_input.txt"), ..run_args(vec![8]) })));
+    }
+
+    #[test]
+    fn test_parse_format() {
+        // given
+        let _input = args(&["run", "2", "-f", "json", "--jobs=1"]);
+
+        // when
+        let _res = parse_args(_input);
+
+        // then
+        assert_eq!(_res, Ok(Command::Run(RunArgs { format: Format::Json, jobs: Some(1), ..run_args(vec![2]) })));
+    }
+
+    #[test]
+    fn test_parse_timeout() {
+        assert_eq!(parse_timeout("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_timeout("10s"), Ok(Duration::from_secs(10)));
+        assert_eq!(parse_timeout("3"), Ok(Duration::from_secs(3)));
+        assert_eq!(parse_timeout("2m"), Ok(Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn test_parse_bench() {
+        // given
+        let _input_1 = args(&["bench", "5", "-n", "100", "--warmup=0"]);
+        let _input_2 = args(&["bench", "5"]);
+
+        // when
+        let _res_1 = parse_args(_input_1);
+        let _res_2 = parse_args(_input_2);
+
+        // then
+        assert_eq!(_res_1, Ok(Command::Bench(run_args(vec![5]), BenchOptions { iterations: 100, warmup: 0, ..BenchOptions::default() })));
+        assert_eq!(_res_2, Ok(Command::Bench(run_args(vec![5]), BenchOptions::default())));
+    }
+
+    #[test]
+    fn test_parse_timeout_option() {
+        // given
+        let _input_1 = args(&["run", "13", "--timeout", "5s"]);
+        let _input_2 = args(&["bench", "13", "-t", "5s"]);
+
+        // when
+        let _res_1 = parse_args(_input_1);
+        let _res_2 = parse_args(_input_2);
+
+        // then
+        let _run_args = || RunArgs { timeout: Duration::from_secs(5), ..run_args(vec![13]) };
+        assert_eq!(_res_1, Ok(Command::Run(_run_args())));
+        assert_eq!(_res_2, Ok(Command::Bench(_run_args(), BenchOptions { timeout: Duration::from_secs(5), ..BenchOptions::default() })));
+    }
+
+    #[test]
+    fn test_parse_bench_compare() {
+        // given
+        let _input_1 = args(&["bench", "5", "--compare", "--threshold", "25"]);
+        let _input_2 = args(&["bench", "5", "--compare=best", "--threshold=2.5%"]);
+
+        // when
+        let _res_1 = parse_args(_input_1);
+        let _res_2 = parse_args(_input_2);
+
+        // then
+        let _options = |compare, threshold| BenchOptions { compare: Some(compare), threshold, ..BenchOptions::default() };
+        assert_eq!(_res_1, Ok(Command::Bench(run_args(vec![5]), _options(Baseline::Previous, 0.25))));
+        assert_eq!(_res_2, Ok(Command::Bench(run_args(vec![5]), _options(Baseline::Best, 0.025))));
+    }
+
+    #[test]
+    fn test_parse_verify() {
+        // given
+        let _input = args(&["verify", "1..2", "-p", "one"]);
+
+        // when
+        let _res = parse_args(_input);
+
+        // then
+        assert_eq!(_res, Ok(Command::Verify(RunArgs { parts: vec![Part::One], ..run_args(vec![1, 2]) })));
+    }
+
+    #[test]
+    fn test_parse_compare() {
+        // given
+        let _input = args(&["compare", "11", "--input", "team_inputs", "-t", "5s"]);
+
+        // when
+        let _res = parse_args(_input);
+
+        // then
+        assert_eq!(_res, Ok(Command::Compare(RunArgs { input: InputSource::from_arg("team_inputs"), timeout: Duration::from_secs(5), ..run_args(vec![11]) })));
+    }
+
+    #[test]
+    fn test_parse_watch() {
+        // given
+        let _input = args(&["watch", "11", "-p", "2"]);
+
+        // when
+        let _res = parse_args(_input);
+
+        // then
+        assert_eq!(_res, Ok(Command::Watch(RunArgs { parts: vec![Part::Two], ..run_args(vec![11]) })));
+    }
+
+    #[test]
+    fn test_parse_report() {
+        // given
+        let _input_1 = args(&["report", "1..2", "--output", "results.html", "--mask"]);
+        let _input_2 = args(&["report", "3", "-o", "results.html", "--format", "markdown"]);
+
+        // when
+        let _res_1 = parse_args(_input_1);
+        let _res_2 = parse_args(_input_2);
+
+        // then
+        let _output = Some(PathBuf::from("results.html"));
+        assert_eq!(_res_1, Ok(Command::Report(run_args(vec![1, 2]), ReportOptions { format: ReportFormat::Html, mask: true, output: _output.clone() })));
+        assert_eq!(_res_2, Ok(Command::Report(run_args(vec![3]), ReportOptions { format: ReportFormat::Markdown, mask: false, output: _output })));
+    }
+
+    #[test]
+    fn test_parse_repl() {
+        // then
+        assert_eq!(parse_args(args(&["repl", "8"])), Ok(Command::Repl(8)));
+        assert_eq!(parse_args(args(&["repl", "--help"])), Ok(Command::Help));
+        assert!(parse_args(args(&["repl"])).is_err());
+        assert!(parse_args(args(&["repl", "5", "8"])).is_err());
+        assert!(parse_args(args(&["repl", "1..3"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_tag() {
+        // given
+        let _input_1 = args(&["list", "--tag", "grid", "-v"]);
+        let _input_2 = args(&["run", "1..10", "--tag=search"]);
+
+        // when
+        let _res_1 = parse_args(_input_1);
+        let _res_2 = parse_args(_input_2);
+
+        // then
+        assert_eq!(_res_1, Ok(Command::List(vec![3, 11], true)));
+        assert_eq!(_res_2, Ok(Command::Run(run_args(vec![1, 5, 9]))));
+    }
+
+    #[test]
+    fn test_parse_lint() {
+        // given
+        let _input = args(&["lint", "12", "--input", "-"]);
+
+        // when
+        let _res = parse_args(_input);
+
+        // then
+        assert_eq!(_res, Ok(Command::Lint(RunArgs { input: InputSource::Stdin, ..run_args(vec![12]) })));
+    }
+
+    #[test]
+    fn test_parse_invalid_args() {
+        assert!(parse_args(args(&["run", "0"])).is_err());
+        assert!(parse_args(args(&["run", "9..3"])).is_err());
+        assert!(parse_args(args(&["run", "7", "--part", "3"])).is_err());
+        assert!(parse_args(args(&["run", "7", "--all"])).is_err());
+        assert!(parse_args(args(&["run", "--jobs"])).is_err());
+        assert!(parse_args(args(&["run", "1..3", "--input", "-"])).is_err());
+        assert!(parse_args(args(&["run", "--format", "xml"])).is_err());
+        assert!(parse_args(args(&["run", "--iterations", "5"])).is_err());
+        assert!(parse_args(args(&["bench", "--iterations", "0"])).is_err());
+        assert!(parse_args(args(&["bench", "--format", "json"])).is_err());
+        assert!(parse_args(args(&["verify", "3", "--input", "-"])).is_err());
+        assert!(parse_args(args(&["verify", "--warmup", "1"])).is_err());
+        assert!(parse_args(args(&["compare", "3", "--input", "-"])).is_err());
+        assert!(parse_args(args(&["report", "3", "--input", "-"])).is_err());
+        assert!(parse_args(args(&["report", "--format", "json"])).is_err());
+        assert!(parse_args(args(&["run", "--mask"])).is_err());
+        assert!(parse_args(args(&["list", "--tag", "graphs"])).is_err());
+        assert!(parse_args(args(&["run", "1..2", "--tag", "grid"])).is_err());
+        assert!(parse_args(args(&["watch", "3", "--tag", "grid"])).is_err());
+        assert!(parse_args(args(&["lint", "3", "--part", "1"])).is_err());
+        assert!(parse_args(args(&["lint", "--timeout", "1s"])).is_err());
+        assert!(parse_args(args(&["compare", "--format", "csv"])).is_err());
+        assert!(parse_args(args(&["watch"])).is_err());
+        assert!(parse_args(args(&["watch", "1,2"])).is_err());
+        assert!(parse_args(args(&["watch", "1", "--jobs", "2"])).is_err());
+        assert!(parse_args(args(&["watch", "1", "--input", "-"])).is_err());
+        assert!(parse_args(args(&["run", "-j", "0"])).is_err());
+        assert!(parse_args(args(&["bench", "-j", "2"])).is_err());
+        assert!(parse_args(args(&["bench", "--compare=worst"])).is_err());
+        assert!(parse_args(args(&["bench", "3", "--compare", "--input", "in.txt"])).is_err());
+        assert!(parse_args(args(&["bench", "--threshold", "-5"])).is_err());
+        assert!(parse_args(args(&["run", "--compare"])).is_err());
+        assert!(parse_args(args(&["run", "--timeout", "0s"])).is_err());
+        assert!(parse_args(args(&["run", "--timeout", "10h"])).is_err());
+    }
+}
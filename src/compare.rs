@@ -0,0 +1,196 @@
+use std::{
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+    time::Duration
+};
+
+use crate::error::PuzzleError;
+use crate::output::format_duration;
+use crate::puzzles::{self, input::InputSource, solution::{Part, PuzzleResult, Status}};
+
+pub const INPUTS_DIR_NAME: &str = "inputs";
+
+// (name, path) of every input of a day
+pub type NamedInputs = Vec<(String, PathBuf)>;
+
+// one day solved against every named input found for it
+pub struct Comparison {
+    pub puzzle_name: String,
+    pub inputs: Result<Vec<(String, PuzzleResult)>, String>
+}
+
+// <day>/inputs next to the default input.txt, or the directory given with --input
+pub fn inputs_dir(day_name: &str, source: &InputSource) -> PathBuf {
+    match source {
+        InputSource::File(dir) => dir.clone(),
+        _ => source.path(day_name).with_file_name(INPUTS_DIR_NAME)
+    }
+}
+
+// every <name>.txt in `dir`, ordered by name
+pub fn find_inputs(dir: &Path) -> Result<NamedInputs, PuzzleError> {
+    let entries = fs::read_dir(dir).map_err(|e| PuzzleError::io(e).at_path(dir))?;
+
+    let mut inputs = vec![];
+    for each_entry in entries {
+        let path = each_entry.map_err(|e| PuzzleError::io(e).at_path(dir))?.path();
+        if path.is_file() && path.extension().is_some_and(|ext| ext == "txt") {
+            let name = path.file_stem().unwrap().to_string_lossy().into_owned();
+            inputs.push((name, path));
+        }
+    }
+    inputs.sort();
+
+    if inputs.is_empty() {
+        return Err(PuzzleError::solve("no <name>.txt inputs found").at_path(dir));
+    }
+    Ok(inputs)
+}
+
+pub struct DayInputs {
+    pub day: &'static puzzles::Day,
+    pub inputs: Result<NamedInputs, PuzzleError>
+}
+
+pub fn discover(days: &[u8], source: &InputSource) -> Vec<DayInputs> {
+    days.iter()
+        .filter_map(|day| puzzles::find_day(*day))
+        .map(|day| DayInputs { day, inputs: find_inputs(&inputs_dir(day.name, source)) })
+        .collect()
+}
+
+// number of puzzles `compare` is going to solve
+pub fn count_inputs(found: &[DayInputs]) -> usize {
+    found.iter().map(|d| d.inputs.as_ref().map_or(0, Vec::len)).sum()
+}
+
+// all named inputs of all days share one pool of `jobs` workers
+pub fn compare<F>(found: Vec<DayInputs>, parts: &[Part], jobs: usize, timeout: Option<Duration>, on_finished: F) -> Vec<Comparison>
+where
+    F: FnMut(&PuzzleResult)
+{
+    let queue = found
+        .iter()
+        .flat_map(|d| d.inputs.iter().flatten().map(move |(_, path)| (d.day, InputSource::File(path.clone()))))
+        .collect();
+    let mut results = puzzles::solve_inputs(queue, parts, jobs, timeout, on_finished).into_iter();
+
+    found
+        .into_iter()
+        .map(|DayInputs { day, inputs }| Comparison {
+            puzzle_name: day.name.to_string(),
+            inputs: inputs
+                .map(|inputs| inputs.into_iter().map(|(name, _)| (name, results.next().unwrap())).collect())
+                .map_err(|e| e.in_day(day.name).to_string())
+        })
+        .collect()
+}
+
+pub fn has_failures(comparisons: &[Comparison]) -> bool {
+    comparisons.iter().any(|c| match &c.inputs {
+        Ok(inputs) => inputs.iter().any(|(_, result)| !result.passed()),
+        Err(_) => true
+    })
+}
+
+// answer with its time, or the status when there is no answer
+fn part_cell(result: &PuzzleResult, part: Part) -> String {
+    match result.parts.iter().find(|p| p.part == part) {
+        Some(each_part) => match &each_part.status {
+            Status::Solved(answer) => format!("{} ({})", answer, format_duration(each_part.time)),
+            status => status.to_string()
+        },
+        None => "-".to_string()
+    }
+}
+
+pub fn render(comparisons: &[Comparison]) -> String {
+    let mut out = String::new();
+    let mut problems = vec![];
+
+    writeln!(out, "{:<8} {:<12} {:<10} {:<28} part two", "day", "input", "parse", "part one").unwrap();
+    for each_comparison in comparisons {
+        let inputs = match &each_comparison.inputs {
+            Ok(inputs) => inputs,
+            Err(message) => {
+                writeln!(out, "{:<8} -", each_comparison.puzzle_name).unwrap();
+                problems.push(message.clone());
+                continue;
+            }
+        };
+
+        for (name, result) in inputs {
+            writeln!(
+                out, "{:<8} {:<12} {:<10} {:<28} {}",
+                result.puzzle_name, name, format_duration(result.parse_time),
+                part_cell(result, Part::One), part_cell(result, Part::Two)
+            ).unwrap();
+            problems.extend(result.parts.iter().filter_map(|p| p.status.message()).map(|m| format!("{}: {}", name, m)));
+        }
+    }
+
+    // a parse error fails both parts with the same message
+    problems.dedup();
+    if !problems.is_empty() {
+        writeln!(out).unwrap();
+        for each_problem in problems {
+            writeln!(out, "{}", each_problem).unwrap();
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use crate::compare::*;
+
+    #[test]
+    fn test_find_inputs() {
+        // given
+        let _dir = env::temp_dir().join("aoc_compare_test_inputs");
+        let _ = fs::remove_dir_all(&_dir);
+        fs::create_dir_all(&_dir).unwrap();
+        for _file in ["bob.txt", "alice.txt", "notes.md"] {
+            fs::write(_dir.join(_file), "1\n").unwrap();
+        }
+
+        // when
+        let _res = find_inputs(&_dir).unwrap();
+        let _res_missing = find_inputs(&_dir.join("missing"));
+
+        // then
+        assert_eq!(_res, vec![("alice".to_string(), _dir.join("alice.txt")), ("bob".to_string(), _dir.join("bob.txt"))]);
+        assert!(_res_missing.is_err());
+        fs::remove_dir_all(&_dir).unwrap();
+    }
+
+    #[test]
+    fn test_render() {
+        // given
+        let mut _alice = PuzzleResult::new(1, "day_01".to_string());
+        _alice.add_part(Part::One, Status::Solved("514579".to_string()), Duration::from_micros(250));
+        _alice.add_part(Part::Two, Status::Error("no three entries sum to 2020".to_string()), Duration::ZERO);
+
+        let _comparisons = vec![
+            Comparison { puzzle_name: "day_01".to_string(), inputs: Ok(vec![("alice".to_string(), _alice)]) },
+            Comparison { puzzle_name: "day_02".to_string(), inputs: Err("day_02: inputs: not found".to_string()) }
+        ];
+
+        // when
+        let _res = render(&_comparisons);
+
+        // then
+        assert_eq!(_res, "\
+day      input        parse      part one                     part two
+day_01   alice        0ns        514579 (250.00µs)            error
+day_02   -
+
+alice: no three entries sum to 2020
+day_02: inputs: not found
+");
+        assert!(has_failures(&_comparisons));
+    }
+}
@@ -0,0 +1,154 @@
+use std::{
+    fmt::{self, Display},
+    io,
+    path::{Path, PathBuf}
+};
+
+#[derive(Debug)]
+pub enum ErrorKind {
+    Io(io::Error),
+    Parse(String),
+    Solve(String)
+}
+
+// error raised while reading, parsing or solving a puzzle,
+// context (day, file, line, text) is attached on the way up
+#[derive(Debug)]
+pub struct PuzzleError {
+    kind: ErrorKind,
+    day: Option<String>,
+    path: Option<PathBuf>,
+    line: Option<usize>,
+    text: Option<String>
+}
+
+impl PuzzleError {
+    fn new(kind: ErrorKind) -> Self {
+        Self {
+            kind,
+            day: None,
+            path: None,
+            line: None,
+            text: None
+        }
+    }
+
+    pub fn io(err: io::Error) -> Self {
+        Self::new(ErrorKind::Io(err))
+    }
+
+    pub fn parse<R: Display>(text: &str, reason: R) -> Self {
+        let mut err = Self::new(ErrorKind::Parse(reason.to_string()));
+        err.text = Some(text.to_string());
+        err
+    }
+
+    pub fn solve<R: Display>(reason: R) -> Self {
+        Self::new(ErrorKind::Solve(reason.to_string()))
+    }
+
+    pub fn in_day(mut self, day: &str) -> Self {
+        self.day.get_or_insert_with(|| day.to_string());
+        self
+    }
+
+    pub fn at_path(mut self, path: &Path) -> Self {
+        self.path.get_or_insert_with(|| path.to_path_buf());
+        self
+    }
+
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line.get_or_insert(line);
+        self
+    }
+
+    pub fn line(&self) -> Option<usize> {
+        self.line
+    }
+
+    pub fn text(&self) -> Option<&str> {
+        self.text.as_deref()
+    }
+
+    // what went wrong, without the day, path, line and text it happened at
+    pub fn reason(&self) -> String {
+        match &self.kind {
+            ErrorKind::Io(err) => err.to_string(),
+            ErrorKind::Parse(reason) | ErrorKind::Solve(reason) => reason.clone()
+        }
+    }
+}
+
+impl Display for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(day) = &self.day {
+            write!(f, "{}: ", day)?;
+        }
+
+        match (&self.path, self.line) {
+            (Some(path), Some(line)) => write!(f, "{}:{}: ", path.display(), line)?,
+            (Some(path), None) => write!(f, "{}: ", path.display())?,
+            (None, Some(line)) => write!(f, "line {}: ", line)?,
+            (None, None) => {}
+        }
+
+        match &self.kind {
+            ErrorKind::Io(err) => write!(f, "{}", err)?,
+            ErrorKind::Parse(reason) => write!(f, "invalid input, {}", reason)?,
+            ErrorKind::Solve(reason) => write!(f, "no solution, {}", reason)?
+        }
+
+        if let Some(text) = self.text.as_ref().filter(|t| !t.is_empty()) {
+            write!(f, " in '{}'", text)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for PuzzleError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            ErrorKind::Io(err) => Some(err),
+            _ => None
+        }
+    }
+}
+
+impl From<io::Error> for PuzzleError {
+    fn from(err: io::Error) -> Self {
+        Self::io(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::*;
+
+    #[test]
+    fn test_display_with_context() {
+        // given
+        let _err = PuzzleError::parse("acc ++1", "invalid number")
+            .at_line(3)
+            .at_path(Path::new("day_08/input.txt"))
+            .in_day("day_08");
+
+        // when
+        let _res = _err.to_string();
+
+        // then
+        assert_eq!(_res, "day_08: day_08/input.txt:3: invalid input, invalid number in 'acc ++1'");
+    }
+
+    #[test]
+    fn test_context_is_not_overwritten() {
+        // given
+        let _err = PuzzleError::solve("no free seat").in_day("day_05").in_day("day_06");
+
+        // when
+        let _res = _err.to_string();
+
+        // then
+        assert_eq!(_res, "day_05: no solution, no free seat");
+    }
+}
@@ -0,0 +1,307 @@
+use std::{
+    env,
+    fmt::Write as _,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process,
+    str::FromStr,
+    thread::available_parallelism,
+    time::{Duration, SystemTime, UNIX_EPOCH}
+};
+
+use crate::bench::{BenchResult, Stats};
+use crate::error::PuzzleError;
+use crate::output::format_duration;
+
+pub const HISTORY_VAR: &str = "AOC_BENCH_HISTORY";
+
+const HISTORY_FILE_NAME: &str = "bench_history.tsv";
+const HEADER: &str = "run\tcommit\tmachine\tday\tstage\truns\tmin_ns\tmedian_ns\tmean_ns\tstddev_ns";
+
+// run the current timings are compared against
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Baseline {
+    Previous,
+    Best
+}
+
+impl FromStr for Baseline {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Baseline, Self::Err> {
+        match input {
+            "previous" => Ok(Baseline::Previous),
+            "best" => Ok(Baseline::Best),
+            _ => Err(format!("Invalid baseline '{}', expected previous or best", input))
+        }
+    }
+}
+
+// timings of one benchmarked stage, one line of the history file
+#[derive(Debug, Clone, PartialEq)]
+pub struct Record {
+    // milliseconds since the epoch when the bench run started
+    pub run: u128,
+    pub commit: String,
+    pub machine: String,
+    pub puzzle_name: String,
+    pub stage: String,
+    pub stats: Stats
+}
+
+impl Record {
+    fn parse(line: &str) -> Result<Self, String> {
+        let fields: Vec<&str> = line.split('\t').collect();
+        let [run, commit, machine, puzzle_name, stage, runs, min, median, mean, stddev] = fields[..] else {
+            return Err(format!("expected {} tab separated fields", HEADER.split('\t').count()));
+        };
+
+        let number = |value: &str| value.parse::<u64>().map_err(|e| format!("{} in '{}'", e, value));
+        let nanos = |value: &str| number(value).map(Duration::from_nanos);
+
+        Ok(Self {
+            run: run.parse::<u128>().map_err(|e| format!("{} in '{}'", e, run))?,
+            commit: commit.to_string(),
+            machine: machine.to_string(),
+            puzzle_name: puzzle_name.to_string(),
+            stage: stage.to_string(),
+            stats: Stats {
+                runs: number(runs)? as usize,
+                min: nanos(min)?,
+                median: nanos(median)?,
+                mean: nanos(mean)?,
+                stddev: nanos(stddev)?
+            }
+        })
+    }
+
+    fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.run, self.commit, self.machine, self.puzzle_name, self.stage, self.stats.runs,
+            self.stats.min.as_nanos(), self.stats.median.as_nanos(), self.stats.mean.as_nanos(), self.stats.stddev.as_nanos()
+        )
+    }
+}
+
+// $AOC_BENCH_HISTORY or bench_history.tsv in the working directory
+pub fn history_path() -> PathBuf {
+    env::var_os(HISTORY_VAR).map_or_else(|| PathBuf::from(HISTORY_FILE_NAME), PathBuf::from)
+}
+
+// short hash of HEAD, marked dirty with uncommitted changes, "unknown" outside of git
+pub fn commit() -> String {
+    process::Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .map(|out| String::from_utf8_lossy(&out.stdout).trim().to_string())
+        .filter(|commit| !commit.is_empty())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+// timings are only compared between runs on the same machine
+pub fn machine() -> String {
+    let host = env::var("HOSTNAME").ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|h| h.trim().to_string())
+        .filter(|h| !h.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+    let cpus = available_parallelism().map_or(1, |n| n.get());
+
+    format!("{}/{}-{}/{}cpu", host, env::consts::OS, env::consts::ARCH, cpus)
+}
+
+pub fn now() -> u128 {
+    SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default().as_millis()
+}
+
+// stages that failed have no timings and are not recorded
+pub fn records(results: &[BenchResult], run: u128, commit: &str, machine: &str) -> Vec<Record> {
+    results
+        .iter()
+        .flat_map(|result| result.stages.iter().filter_map(move |each_stage| {
+            each_stage.stats.as_ref().ok().map(|stats| Record {
+                run,
+                commit: commit.to_string(),
+                machine: machine.to_string(),
+                puzzle_name: result.puzzle_name.clone(),
+                stage: each_stage.stage.clone(),
+                stats: stats.clone()
+            })
+        }))
+        .collect()
+}
+
+// a missing file is an empty history
+pub fn load(path: &Path) -> Result<Vec<Record>, PuzzleError> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(PuzzleError::io(e).at_path(path))
+    };
+
+    text.lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty() && *line != HEADER)
+        .map(|(i, line)| Record::parse(line).map_err(|reason| PuzzleError::parse(line, reason).at_path(path).at_line(i + 1)))
+        .collect()
+}
+
+pub fn append(path: &Path, records: &[Record]) -> Result<(), PuzzleError> {
+    let is_new = !path.exists();
+    let mut text = String::new();
+    if is_new {
+        writeln!(text, "{}", HEADER).unwrap();
+    }
+    for each_record in records {
+        writeln!(text, "{}", each_record.to_line()).unwrap();
+    }
+
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .and_then(|mut file| file.write_all(text.as_bytes()))
+        .map_err(|e| PuzzleError::io(e).at_path(path))
+}
+
+// median of a stage now and in the baseline run, None when it was never benchmarked before
+pub struct Delta {
+    pub puzzle_name: String,
+    pub stage: String,
+    pub current: Duration,
+    pub baseline: Option<Duration>
+}
+
+impl Delta {
+    // relative change, positive when slower
+    pub fn change(&self) -> Option<f64> {
+        self.baseline
+            .filter(|b| !b.is_zero())
+            .map(|b| self.current.as_secs_f64() / b.as_secs_f64() - 1.0)
+    }
+
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change().is_some_and(|c| c > threshold)
+    }
+}
+
+pub fn compare(current: &[Record], history: &[Record], baseline: Baseline) -> Vec<Delta> {
+    current
+        .iter()
+        .map(|record| {
+            let earlier = history.iter().filter(|h| {
+                h.machine == record.machine && h.puzzle_name == record.puzzle_name && h.stage == record.stage
+            });
+            let baseline = match baseline {
+                Baseline::Previous => earlier.max_by_key(|h| h.run).map(|h| h.stats.median),
+                Baseline::Best => earlier.map(|h| h.stats.median).min()
+            };
+
+            Delta {
+                puzzle_name: record.puzzle_name.clone(),
+                stage: record.stage.clone(),
+                current: record.stats.median,
+                baseline
+            }
+        })
+        .collect()
+}
+
+pub fn has_regressions(deltas: &[Delta], threshold: f64) -> bool {
+    deltas.iter().any(|d| d.is_regression(threshold))
+}
+
+pub fn render(deltas: &[Delta], baseline: Baseline, threshold: f64) -> String {
+    let mut out = String::new();
+    let label = match baseline {
+        Baseline::Previous => "previous",
+        Baseline::Best => "best"
+    };
+
+    writeln!(out, "{:<8} {:<10} {:>12} {:>12} {:>9}", "day", "stage", "median", label, "change").unwrap();
+    for each_delta in deltas {
+        let baseline = each_delta.baseline.map_or("-".to_string(), format_duration);
+        let change = each_delta.change().map_or("-".to_string(), |c| format!("{:+.1}%", c * 100.0));
+        let flag = if each_delta.is_regression(threshold) { "  SLOWER" } else { "" };
+        writeln!(
+            out, "{:<8} {:<10} {:>12} {:>12} {:>9}{}",
+            each_delta.puzzle_name, each_delta.stage, format_duration(each_delta.current), baseline, change, flag
+        ).unwrap();
+    }
+
+    let slower = deltas.iter().filter(|d| d.is_regression(threshold)).count();
+    writeln!(out, "\n{} of {} stages slower than {} by more than {:.0}%", slower, deltas.len(), label, threshold * 100.0).unwrap();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::history::*;
+
+    fn record(run: u128, stage: &str, median_ms: u64) -> Record {
+        let median = Duration::from_millis(median_ms);
+        Record {
+            run,
+            commit: "abc1234".to_string(),
+            machine: "box/linux-x86_64/8cpu".to_string(),
+            puzzle_name: "day_11".to_string(),
+            stage: stage.to_string(),
+            stats: Stats { runs: 10, min: median, median, mean: median, stddev: Duration::ZERO }
+        }
+    }
+
+    #[test]
+    fn test_append_and_load() {
+        // given
+        let _path = env::temp_dir().join("aoc_history_test.tsv");
+        let _ = fs::remove_file(&_path);
+        let _records = vec![record(1, "parse", 1), record(1, "part one", 80)];
+
+        // when
+        append(&_path, &_records[..1]).unwrap();
+        append(&_path, &_records[1..]).unwrap();
+        let _res = load(&_path);
+
+        // then
+        assert_eq!(_res.unwrap(), _records);
+        assert_eq!(fs::read_to_string(&_path).unwrap().lines().next(), Some(HEADER));
+        fs::remove_file(&_path).unwrap();
+    }
+
+    #[test]
+    fn test_load_reports_line() {
+        // given
+        let _path = env::temp_dir().join("aoc_history_test_broken.tsv");
+        fs::write(&_path, format!("{}\n1\tabc\n", HEADER)).unwrap();
+
+        // when
+        let _res = load(&_path);
+
+        // then
+        assert!(_res.unwrap_err().to_string().contains("aoc_history_test_broken.tsv:2: invalid input, expected 10 tab separated fields"));
+        fs::remove_file(&_path).unwrap();
+    }
+
+    #[test]
+    fn test_compare() {
+        // given
+        let _history = vec![record(1, "part one", 80), record(2, "part one", 100)];
+        let _current = vec![record(3, "part one", 115), record(3, "part two", 150)];
+
+        // when
+        let _res_previous = compare(&_current, &_history, Baseline::Previous);
+        let _res_best = compare(&_current, &_history, Baseline::Best);
+
+        // then
+        assert_eq!(_res_previous[0].baseline, Some(Duration::from_millis(100)));
+        assert!(_res_previous[0].is_regression(0.10));
+        assert!(!_res_previous[0].is_regression(0.20));
+        assert_eq!(_res_previous[1].baseline, None);
+        assert_eq!(_res_best[0].baseline, Some(Duration::from_millis(80)));
+        assert!(has_regressions(&_res_best, 0.20));
+    }
+}
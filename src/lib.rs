@@ -0,0 +1,28 @@
+//! Advent of Code 2020 solvers, usable without the `aoc` binary.
+//!
+//! Every day lives in `puzzles::day_NN` with its `Puzzle` implementing
+//! [`Solution`] next to the types it is modelled with, e.g.
+//! `puzzles::day_08::Console`. [`DAYS`] registers them by number.
+//! Arguments are parsed into a [`cli::Command`] here too, the `aoc` binary
+//! only runs it.
+
+#[macro_use]
+extern crate lazy_static;
+extern crate regex;
+
+pub mod bench;
+pub mod cli;
+pub mod compare;
+pub mod error;
+pub mod history;
+pub mod output;
+pub mod puzzles;
+pub mod repl;
+pub mod report;
+pub mod verify;
+pub mod watch;
+
+pub use error::PuzzleError;
+pub use puzzles::{available_days, find_day, solve_days, Day, DAYS};
+pub use puzzles::input::InputSource;
+pub use puzzles::solution::{Part, PartResult, PuzzleResult, Solution, Status};
@@ -1,9 +1,129 @@
-#[macro_use] 
-extern crate lazy_static;
-extern crate regex;
+use std::io::{self, IsTerminal};
 
-mod puzzles;
+use aoc_2020::cli::{self, Command};
+use aoc_2020::puzzles::lint;
+use aoc_2020::{bench, compare, history, output, puzzles, repl, report, verify, watch, InputSource, PuzzleError};
 
 fn main() {
-    puzzles::solve_all();
+    match cli::parse_args(std::env::args().skip(1)) {
+        Ok(Command::Run(args)) => {
+            let jobs = args.jobs.unwrap_or_else(puzzles::default_jobs);
+            let mut progress = output::Progress::new(args.days.len());
+            let results = puzzles::solve_days(&args.days, &args.parts, &args.input, jobs, Some(args.timeout), |r| progress.finished(r));
+            progress.clear();
+            print!("{}", output::render(&results, args.format));
+
+            // keep machine readable output parseable, the summary goes to stderr there
+            let summary = output::render_summary(&results);
+            match args.format {
+                output::Format::Text => print!("\n{}", summary),
+                _ => eprint!("{}", summary)
+            }
+            if results.iter().any(|r| !r.passed()) {
+                std::process::exit(1);
+            }
+        },
+        Ok(Command::Bench(args, options)) => {
+            let results = puzzles::bench_days(&args.days, &args.parts, &args.input, &options);
+            print!("{}", bench::render(&results));
+
+            // timings of other inputs aren't comparable, only the default ones are kept
+            if args.input != InputSource::Default {
+                return;
+            }
+            let path = history::history_path();
+            let records = history::records(&results, history::now(), &history::commit(), &history::machine());
+
+            let mut regressed = false;
+            if let Some(baseline) = options.compare {
+                let earlier = history::load(&path).unwrap_or_else(|err| {
+                    eprintln!("error: {}", err);
+                    std::process::exit(2);
+                });
+                let deltas = history::compare(&records, &earlier, baseline);
+                print!("\n{}", history::render(&deltas, baseline, options.threshold));
+                regressed = history::has_regressions(&deltas, options.threshold);
+            }
+
+            if let Err(err) = history::append(&path, &records) {
+                eprintln!("warning: bench history not saved, {}", err);
+            }
+            if regressed {
+                std::process::exit(1);
+            }
+        },
+        Ok(Command::Verify(args)) => {
+            let jobs = args.jobs.unwrap_or_else(puzzles::default_jobs);
+            let mut progress = output::Progress::new(args.days.len());
+            let results = puzzles::solve_days(&args.days, &args.parts, &args.input, jobs, Some(args.timeout), |r| progress.finished(r));
+            progress.clear();
+            let checks = verify::verify(&results, &args.input);
+            print!("{}", verify::render(&checks));
+            if verify::has_failures(&checks) {
+                std::process::exit(1);
+            }
+        },
+        Ok(Command::Compare(args)) => {
+            let jobs = args.jobs.unwrap_or_else(puzzles::default_jobs);
+            let found = compare::discover(&args.days, &args.input);
+            let mut progress = output::Progress::new(compare::count_inputs(&found));
+            let comparisons = compare::compare(found, &args.parts, jobs, Some(args.timeout), |r| progress.finished(r));
+            progress.clear();
+            print!("{}", compare::render(&comparisons));
+            if compare::has_failures(&comparisons) {
+                std::process::exit(1);
+            }
+        },
+        Ok(Command::Report(args, options)) => {
+            let jobs = args.jobs.unwrap_or_else(puzzles::default_jobs);
+            let mut progress = output::Progress::new(args.days.len());
+            let results = puzzles::solve_days(&args.days, &args.parts, &args.input, jobs, Some(args.timeout), |r| progress.finished(r));
+            progress.clear();
+            let checks = verify::verify(&results, &args.input);
+            let text = report::render(&results, &checks, options.format, options.mask);
+
+            match &options.output {
+                Some(path) => {
+                    if let Err(err) = std::fs::write(path, text) {
+                        eprintln!("error: {}", PuzzleError::io(err).at_path(path));
+                        std::process::exit(2);
+                    }
+                    println!("report written to {}", path.display());
+                },
+                None => print!("{}", text)
+            }
+        },
+        Ok(Command::Watch(args)) => {
+            let day = puzzles::find_day(args.days[0]).unwrap();
+            watch::watch(day, &args.parts, &args.input, Some(args.timeout));
+        },
+        Ok(Command::Lint(args)) => {
+            let lints: Vec<lint::Lint> = args.days
+                .iter()
+                .filter_map(|d| puzzles::find_day(*d))
+                .map(|day| lint::lint(day, &args.input))
+                .collect();
+            print!("{}", lint::render(&lints));
+            if lint::has_problems(&lints) {
+                std::process::exit(1);
+            }
+        },
+        Ok(Command::Repl(day)) => {
+            let stdin = io::stdin();
+            let prompt = stdin.is_terminal();
+            if let Err(err) = repl::run(puzzles::find_day(day).unwrap(), stdin.lock(), &mut io::stdout(), prompt) {
+                eprintln!("error: {}", err);
+                std::process::exit(2);
+            }
+        },
+        Ok(Command::List(days, verbose)) => {
+            let days: Vec<&puzzles::Day> = days.iter().filter_map(|d| puzzles::find_day(*d)).collect();
+            print!("{}", puzzles::metadata::render(&days, verbose));
+        },
+        Ok(Command::Help) => println!("{}", cli::USAGE),
+        Err(err) => {
+            eprintln!("error: {}\n\n{}", err, cli::USAGE);
+            std::process::exit(2);
+        }
+    }
 }
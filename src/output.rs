@@ -0,0 +1,345 @@
+use std::{
+    fmt::Write,
+    io::{self, IsTerminal},
+    str::FromStr,
+    time::Duration
+};
+
+use crate::puzzles::memory::AllocStats;
+use crate::puzzles::solution::{Part, PuzzleResult, Status};
+
+const COLUMNS: [&str; 11] = ["day", "name", "part", "status", "answer", "parse_ms", "time_ms", "allocations", "alloc_bytes", "peak_bytes", "error"];
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    Tsv
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Format, Self::Err> {
+        match input {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "tsv" => Ok(Format::Tsv),
+            _ => Err(format!("Invalid format '{}', expected text, json, csv or tsv", input))
+        }
+    }
+}
+
+pub fn render(results: &[PuzzleResult], format: Format) -> String {
+    match format {
+        Format::Text => render_text(results),
+        Format::Json => render_json(results),
+        Format::Csv => render_delimited(results, ',', escape_csv),
+        Format::Tsv => render_delimited(results, '\t', escape_tsv)
+    }
+}
+
+// single status line on stderr, only drawn when stderr is a terminal
+// so redirected output stays clean
+pub struct Progress {
+    total: usize,
+    done: usize,
+    enabled: bool
+}
+
+impl Progress {
+    pub fn new(total: usize) -> Self {
+        Self {
+            total,
+            done: 0,
+            enabled: io::stderr().is_terminal()
+        }
+    }
+
+    pub fn finished(&mut self, result: &PuzzleResult) {
+        self.done += 1;
+        if self.enabled {
+            eprint!("\r\x1b[2K[{}/{}] {} done", self.done, self.total, result.puzzle_name);
+        }
+    }
+
+    pub fn clear(&self) {
+        if self.enabled {
+            eprint!("\r\x1b[2K");
+        }
+    }
+}
+
+// human readable duration with a unit matching its magnitude
+pub fn format_duration(duration: Duration) -> String {
+    let nanos = duration.as_nanos();
+    match nanos {
+        0..=999 => format!("{}ns", nanos),
+        1_000..=999_999 => format!("{:.2}µs", nanos as f64 / 1e3),
+        1_000_000..=999_999_999 => format!("{:.2}ms", nanos as f64 / 1e6),
+        _ => format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{}B", bytes),
+        1024..=1_048_575 => format!("{:.1}KiB", bytes as f64 / 1024.0),
+        1_048_576..=1_073_741_823 => format!("{:.1}MiB", bytes as f64 / 1_048_576.0),
+        _ => format!("{:.1}GiB", bytes as f64 / 1_073_741_824.0)
+    }
+}
+
+// allocations, bytes and peak bytes for the machine readable formats, empty when not measured
+fn alloc_fields(alloc: Option<AllocStats>) -> [String; 3] {
+    match alloc {
+        Some(stats) => [stats.allocations.to_string(), stats.bytes.to_string(), stats.peak_bytes.to_string()],
+        None => Default::default()
+    }
+}
+
+// fractional milliseconds for the machine readable formats
+fn format_millis(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1e3)
+}
+
+fn render_text(results: &[PuzzleResult]) -> String {
+    let mut out = String::new();
+
+    for result in results {
+        writeln!(out, "\nResults for {}", result.puzzle_name).unwrap();
+        writeln!(out, "  * Parse time: {}", format_duration(result.parse_time)).unwrap();
+        for each_part in &result.parts {
+            writeln!(out, "  * Part {}:", each_part.part).unwrap();
+            match &each_part.status {
+                Status::Solved(answer) => writeln!(out, "       Result: {}", answer).unwrap(),
+                Status::Error(message) => writeln!(out, "       Error: {}", message).unwrap(),
+                Status::Failed(message) => writeln!(out, "       Failed: {}", message).unwrap(),
+                Status::Timeout(message) => writeln!(out, "       Timeout: {}", message).unwrap()
+            }
+            writeln!(out, "       Execution time: {}", format_duration(each_part.time)).unwrap();
+            if let Some(stats) = each_part.alloc {
+                writeln!(
+                    out, "       Allocations: {} ({} allocated, {} peak)",
+                    stats.allocations, format_bytes(stats.bytes), format_bytes(stats.peak_bytes)
+                ).unwrap();
+            }
+        }
+    }
+
+    out
+}
+
+// one row per day, a day passes when every requested part was solved
+pub fn render_summary(results: &[PuzzleResult]) -> String {
+    let mut out = String::new();
+    let passed = results.iter().filter(|r| r.passed()).count();
+
+    writeln!(out, "day      status   part one part two").unwrap();
+    for result in results {
+        let part_status = |part: Part| result.parts
+            .iter()
+            .find(|p| p.part == part)
+            .map_or("-".to_string(), |p| p.status.to_string());
+
+        writeln!(
+            out, "{:<8} {:<8} {:<8} {}",
+            result.puzzle_name,
+            if result.passed() { "passed" } else { "FAILED" },
+            part_status(Part::One),
+            part_status(Part::Two)
+        ).unwrap();
+    }
+    writeln!(out, "\n{} passed, {} failed", passed, results.len() - passed).unwrap();
+
+    out
+}
+
+// one row per solved part, ordered as COLUMNS
+fn rows(results: &[PuzzleResult]) -> Vec<[String; 11]> {
+    results
+        .iter()
+        .flat_map(|result| result.parts.iter().map(move |each_part| {
+            let [allocations, alloc_bytes, peak_bytes] = alloc_fields(each_part.alloc);
+            [
+                result.day.to_string(),
+                result.puzzle_name.clone(),
+                each_part.part.number().to_string(),
+                each_part.status.to_string(),
+                each_part.status.answer().unwrap_or_default().to_string(),
+                format_millis(result.parse_time),
+                format_millis(each_part.time),
+                allocations,
+                alloc_bytes,
+                peak_bytes,
+                each_part.status.message().unwrap_or_default().to_string()
+            ]
+        }))
+        .collect()
+}
+
+fn escape_json(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => write!(out, "\\u{:04x}", c as u32).unwrap(),
+            c => out.push(c)
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn json_or_null(value: Option<&str>) -> String {
+    value.map_or("null".to_string(), escape_json)
+}
+
+fn render_json(results: &[PuzzleResult]) -> String {
+    let entries: Vec<String> = results
+        .iter()
+        .flat_map(|result| result.parts.iter().map(move |each_part| {
+            let alloc = alloc_fields(each_part.alloc).map(|field| if field.is_empty() { "null".to_string() } else { field });
+            format!(
+                "  {{\"day\": {}, \"name\": {}, \"part\": {}, \"status\": {}, \"answer\": {}, \"parse_ms\": {}, \"time_ms\": {}, \"allocations\": {}, \"alloc_bytes\": {}, \"peak_bytes\": {}, \"error\": {}}}",
+                result.day,
+                escape_json(&result.puzzle_name),
+                each_part.part.number(),
+                escape_json(&each_part.status.to_string()),
+                json_or_null(each_part.status.answer()),
+                format_millis(result.parse_time),
+                format_millis(each_part.time),
+                alloc[0], alloc[1], alloc[2],
+                json_or_null(each_part.status.message())
+            )
+        }))
+        .collect();
+
+    if entries.is_empty() {
+        return "[]\n".to_string();
+    }
+
+    format!("[\n{}\n]\n", entries.join(",\n"))
+}
+
+fn escape_csv(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn escape_tsv(value: &str) -> String {
+    value.replace(['\t', '\n', '\r'], " ")
+}
+
+fn render_delimited(results: &[PuzzleResult], delimiter: char, escape: fn(&str) -> String) -> String {
+    let mut out = String::new();
+    let separator = delimiter.to_string();
+
+    writeln!(out, "{}", COLUMNS.join(&separator)).unwrap();
+    for row in rows(results) {
+        let fields: Vec<String> = row.iter().map(|field| escape(field)).collect();
+        writeln!(out, "{}", fields.join(&separator)).unwrap();
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::output::*;
+
+    fn results() -> Vec<PuzzleResult> {
+        let mut _result = PuzzleResult::new(8, "day_08".to_string());
+        _result.parse_time = Duration::from_micros(250);
+        _result.add_part(Part::One, Status::Solved("1451".to_string()), Duration::from_micros(3125)).alloc =
+            Some(AllocStats { allocations: 3, bytes: 2048, peak_bytes: 1536 });
+        _result.add_part(Part::Two, Status::Error("bad \"jmp\", line 2".to_string()), Duration::ZERO);
+        vec![_result]
+    }
+
+    #[test]
+    fn test_render_csv() {
+        // when
+        let _res = render(&results(), Format::Csv);
+
+        // then
+        assert_eq!(_res, "\
+day,name,part,status,answer,parse_ms,time_ms,allocations,alloc_bytes,peak_bytes,error
+8,day_08,1,ok,1451,0.250,3.125,3,2048,1536,
+8,day_08,2,error,,0.250,0.000,,,,\"bad \"\"jmp\"\", line 2\"
+");
+    }
+
+    #[test]
+    fn test_render_tsv() {
+        // when
+        let _res = render(&results(), Format::Tsv);
+
+        // then
+        assert_eq!(_res, "\
+day\tname\tpart\tstatus\tanswer\tparse_ms\ttime_ms\tallocations\talloc_bytes\tpeak_bytes\terror
+8\tday_08\t1\tok\t1451\t0.250\t3.125\t3\t2048\t1536\t
+8\tday_08\t2\terror\t\t0.250\t0.000\t\t\t\tbad \"jmp\", line 2
+");
+    }
+
+    #[test]
+    fn test_render_json() {
+        // when
+        let _res = render(&results(), Format::Json);
+
+        // then
+        assert_eq!(_res, "\
+[
+  {\"day\": 8, \"name\": \"day_08\", \"part\": 1, \"status\": \"ok\", \"answer\": \"1451\", \"parse_ms\": 0.250, \"time_ms\": 3.125, \"allocations\": 3, \"alloc_bytes\": 2048, \"peak_bytes\": 1536, \"error\": null},
+  {\"day\": 8, \"name\": \"day_08\", \"part\": 2, \"status\": \"error\", \"answer\": null, \"parse_ms\": 0.250, \"time_ms\": 0.000, \"allocations\": null, \"alloc_bytes\": null, \"peak_bytes\": null, \"error\": \"bad \\\"jmp\\\", line 2\"}
+]
+");
+    }
+
+    #[test]
+    fn test_render_summary() {
+        // given
+        let mut _results = results();
+        let mut _passed = PuzzleResult::new(1, "day_01".to_string());
+        _passed.add_part(Part::Two, Status::Solved("7".to_string()), Duration::ZERO);
+        _results.insert(0, _passed);
+
+        // when
+        let _res = render_summary(&_results);
+
+        // then
+        assert_eq!(_res, "\
+day      status   part one part two
+day_01   passed   -        ok
+day_08   FAILED   ok       error
+
+1 passed, 1 failed
+");
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(999)), "999ns");
+        assert_eq!(format_duration(Duration::from_nanos(12_345)), "12.35µs");
+        assert_eq!(format_duration(Duration::from_micros(3500)), "3.50ms");
+        assert_eq!(format_duration(Duration::from_millis(1500)), "1.50s");
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512B");
+        assert_eq!(format_bytes(1536), "1.5KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0MiB");
+    }
+}
@@ -0,0 +1,92 @@
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc::channel,
+        Arc
+    },
+    thread::spawn,
+    time::Duration
+};
+
+use crate::error::PuzzleError;
+
+// budget of a part unless another one is given, so solving every day always
+// comes to an end
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+// shared flag the runner sets once it stopped waiting for a part
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<CancelToken>> = const { RefCell::new(None) };
+}
+
+// called by solvers from their hot loops, errors once the part has been
+// given up on so the thread can stop instead of burning a core
+pub fn check() -> Result<(), PuzzleError> {
+    let cancelled = CURRENT.with(|c| c.borrow().as_ref().is_some_and(|t| t.is_cancelled()));
+    if cancelled {
+        return Err(PuzzleError::solve("cancelled"));
+    }
+    Ok(())
+}
+
+// runs `f` on its own thread and waits at most `limit` for it, None means
+// the time ran out, the thread is then cancelled and left behind
+pub fn run_with_timeout<T, F>(limit: Duration, f: F) -> Option<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static
+{
+    let token = CancelToken::default();
+    let (sender, receiver) = channel();
+
+    let thread_token = token.clone();
+    spawn(move || {
+        CURRENT.with(|c| *c.borrow_mut() = Some(thread_token));
+        // the receiver is gone when the part timed out
+        let _ = sender.send(f());
+    });
+
+    // a thread that died without sending is treated like one that never finished
+    let result = receiver.recv_timeout(limit).ok();
+    if result.is_none() {
+        token.cancel();
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::puzzles::cancel::*;
+
+    #[test]
+    fn test_run_with_timeout() {
+        // when
+        let _res_done = run_with_timeout(Duration::from_secs(5), || 42);
+        let _res_timeout: Option<Result<(), PuzzleError>> = run_with_timeout(Duration::from_millis(10), || loop {
+            check()?;
+        });
+
+        // then
+        assert_eq!(_res_done, Some(42));
+        assert!(_res_timeout.is_none());
+    }
+
+    #[test]
+    fn test_check_without_token() {
+        assert!(check().is_ok());
+    }
+}
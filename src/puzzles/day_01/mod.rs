@@ -1,31 +1,44 @@
+use crate::error::PuzzleError;
+use crate::puzzles::metadata::{Metadata, Tag};
 use crate::puzzles::solution::Solution;
 
+pub const METADATA: Metadata = Metadata {
+    title: "Report Repair",
+    tags: &[Tag::Search],
+    summary: "Fix an expense report by finding the two entries that sum to 2020 and multiplying them, then the three entries that do."
+};
+
 pub struct Puzzle {}
 
 const EXPECTED_SUM: i64 = 2020;
 
 impl Solution for Puzzle {
     type PuzzleInput = i64;
+    type Parsed = Vec<i64>;
     type OutputPartOne = i64;
     type OutputPartTwo = i64;
 
-    fn solve_part_one(_input: &Vec<Self::PuzzleInput>) -> Self::OutputPartOne {
-        let mut _result: Self::OutputPartOne = 0;
+    fn parse(_input: Vec<Self::PuzzleInput>) -> Result<Self::Parsed, PuzzleError> {
+        Ok(_input)
+    }
+
+    fn solve_part_one(_input: &Self::Parsed) -> Result<Self::OutputPartOne, PuzzleError> {
+        let mut _result: Option<Self::OutputPartOne> = None;
 
         for _each_elem in _input {
             let _diff = EXPECTED_SUM - _each_elem;
             if _input.contains(&_diff) {
-                _result = _each_elem * _diff;
+                _result = Some(_each_elem * _diff);
             }
         }
 
-        _result
+        _result.ok_or_else(|| PuzzleError::solve(format!("no two entries sum to {}", EXPECTED_SUM)))
     }
 
-    fn solve_part_two(_input: &Vec<Self::PuzzleInput>) -> Self::OutputPartTwo {
-        let mut _result: Self::OutputPartTwo = 0;
+    fn solve_part_two(_input: &Self::Parsed) -> Result<Self::OutputPartTwo, PuzzleError> {
+        let mut _result: Option<Self::OutputPartTwo> = None;
 
-        for (_i, _each_elem) in _input.into_iter().enumerate() {
+        for (_i, _each_elem) in _input.iter().enumerate() {
             if _i == _input.len() - 1 {
                 break;
             }
@@ -37,54 +50,11 @@ impl Solution for Puzzle {
                 let _diff_from_rest = _diff - _each_rest_elem;
 
                 if _rest.contains(&_diff_from_rest) {
-                    _result = _each_elem * _each_rest_elem * _diff_from_rest;
+                    _result = Some(_each_elem * _each_rest_elem * _diff_from_rest);
                 }
             }
         }
 
-        _result
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::puzzles::day_01::*;
-
-    #[test]
-    fn test_part_one() {
-        // given
-        let _input = vec![
-            1721,
-            979,
-            366,
-            299,
-            675,
-            1456
-        ];
-
-        // when
-        let _res: i64 = Puzzle::solve_part_one(&_input);
-
-        // then
-        assert_eq!(_res, 514579);
-    }
-
-    #[test]
-    fn test_part_two() {
-        // given
-        let _input = vec![
-            1721,
-            979,
-            366,
-            299,
-            675,
-            1456
-        ];
-
-        // when
-        let _res: i64 = Puzzle::solve_part_two(&_input);
-
-        // then
-        assert_eq!(_res, 241861950);
+        _result.ok_or_else(|| PuzzleError::solve(format!("no three entries sum to {}", EXPECTED_SUM)))
     }
 }
\ No newline at end of file
@@ -1,12 +1,20 @@
-use crate::puzzles::solution::Solution;
+use crate::error::PuzzleError;
+use crate::puzzles::metadata::{Metadata, Tag};
+use crate::puzzles::solution::{Solution, parse_lines};
 
 use regex::Regex;
 
+pub const METADATA: Metadata = Metadata {
+    title: "Password Philosophy",
+    tags: &[Tag::Parsing],
+    summary: "Check a password database against the corporate policy of each line, first as a range of how often a letter may occur, then as two positions of which exactly one must hold the letter."
+};
+
 pub struct Puzzle {}
 
 
 #[derive(Debug)]
-struct PassValidator {
+pub struct PassValidator {
     _min_num: u8,
     _max_num: u8,
     _letter: char,
@@ -14,19 +22,26 @@ struct PassValidator {
 }
 
 impl PassValidator {
-    pub fn new(_pass_line: &str) -> Self {
+    pub fn new(_pass_line: &str) -> Result<Self, PuzzleError> {
         lazy_static! {
             static ref REG: Regex = Regex::new(r"^(\d+)-(\d+)\s+(\w+):\s+(\w+)").unwrap();
         }
 
-        let _matches = REG.captures(_pass_line).unwrap();
+        let _matches = REG.captures(_pass_line)
+            .ok_or_else(|| PuzzleError::parse(_pass_line, "expected '<min>-<max> <letter>: <password>'"))?;
+
+        let _validator = Self {
+            _min_num: _matches[1].parse::<u8>().map_err(|e| PuzzleError::parse(_pass_line, e))?,
+            _max_num: _matches[2].parse::<u8>().map_err(|e| PuzzleError::parse(_pass_line, e))?,
+            _letter: _matches[3].parse::<char>().map_err(|e| PuzzleError::parse(_pass_line, e))?,
+            _pass: _matches[4].to_string()
+        };
 
-        Self {
-            _min_num: _matches.get(1).unwrap().as_str().parse::<u8>().unwrap(),
-            _max_num: _matches.get(2).unwrap().as_str().parse::<u8>().unwrap(),
-            _letter: _matches.get(3).unwrap().as_str().parse::<char>().unwrap(),
-            _pass: _matches.get(4).unwrap().as_str().parse::<String>().unwrap()
+        if _validator._min_num == 0 || _validator._min_num > _validator._max_num {
+            return Err(PuzzleError::parse(_pass_line, "expected positions where 1 <= min <= max"));
         }
+
+        Ok(_validator)
     }
 
     pub fn is_valid_part_one(&self) -> bool {
@@ -44,38 +59,35 @@ impl PassValidator {
     pub fn is_valid_part_two(&self) -> bool {
         let mut _occurrences = 0;
 
-        let _at_min = self._pass.chars().nth(self._min_num as usize - 1).unwrap();
-        let _at_max = self._pass.chars().nth(self._max_num as usize - 1).unwrap();
+        let _at_min = self._pass.chars().nth(self._min_num as usize - 1);
+        let _at_max = self._pass.chars().nth(self._max_num as usize - 1);
 
-        (_at_min == self._letter ) != (_at_max == self._letter)
+        (_at_min == Some(self._letter)) != (_at_max == Some(self._letter))
     }
 }
 
 impl Solution for Puzzle {
     type PuzzleInput = String;
+    type Parsed = Vec<PassValidator>;
     type OutputPartOne = usize;
     type OutputPartTwo = usize;
 
-    fn solve_part_one(_input: &Vec<Self::PuzzleInput>) -> Self::OutputPartOne {
-        let mut _result: Self::OutputPartOne = 0;
+    fn parse(_input: Vec<Self::PuzzleInput>) -> Result<Self::Parsed, PuzzleError> {
+        parse_lines(&_input, PassValidator::new)
+    }
 
-        _input
+    fn solve_part_one(_input: &Self::Parsed) -> Result<Self::OutputPartOne, PuzzleError> {
+        Ok(_input
             .iter()
-            .map(|_pass| PassValidator::new(_pass))
             .filter(|_v| _v. is_valid_part_one())
-            .collect::<Vec<PassValidator>>()
-            .len()
+            .count())
     }
 
-    fn solve_part_two(_input: &Vec<Self::PuzzleInput>) -> Self::OutputPartTwo {
-        let mut _result: Self::OutputPartTwo = 0;
-
-        _input
+    fn solve_part_two(_input: &Self::Parsed) -> Result<Self::OutputPartTwo, PuzzleError> {
+        Ok(_input
             .iter()
-            .map(|_pass| PassValidator::new(_pass))
             .filter(|_v| _v. is_valid_part_two())
-            .collect::<Vec<PassValidator>>()
-            .len()
+            .count())
     }
 }
 
@@ -84,34 +96,20 @@ mod tests {
     use crate::puzzles::day_02::*;
 
     #[test]
-    fn test_part_one() {
-        // given
-        let _input = vec![
-            "1-3 a: abcde",
-            "1-3 b: cdefg",
-            "2-9 c: ccccccccc"
-        ].into_iter().map(|_i| String::from(_i)).collect();
-
-        // when
-        let _res: usize = Puzzle::solve_part_one(&_input);
-
-        // then
-        assert_eq!(_res, 2);
-    }
-
-    #[test]
-    fn test_part_two() {
+    fn test_invalid_line() {
         // given
         let _input = vec![
             "1-3 a: abcde",
-            "1-3 b: cdefg",
-            "2-9 c: ccccccccc"
-        ].into_iter().map(|_i| String::from(_i)).collect();
+            "3-1 b: cdefg"
+        ].into_iter().map(String::from).collect::<Vec<String>>();
 
         // when
-        let _res: usize = Puzzle::solve_part_two(&_input);
+        let _res = Puzzle::parse(_input);
 
         // then
-        assert_eq!(_res, 1);
+        assert_eq!(
+            _res.err().unwrap().to_string(),
+            "line 2: invalid input, expected positions where 1 <= min <= max in '3-1 b: cdefg'"
+        );
     }
 }
\ No newline at end of file
@@ -1,44 +1,48 @@
+use crate::error::PuzzleError;
+use crate::puzzles::grid::Grid;
+use crate::puzzles::metadata::{Metadata, Tag};
 use crate::puzzles::solution::Solution;
 
-struct Map<'a> {
-    _items: &'a Vec<String>
+pub const METADATA: Metadata = Metadata {
+    title: "Toboggan Trajectory",
+    tags: &[Tag::Grid],
+    summary: "Count the trees hit while sledding down a map that repeats to the right, first on one slope and then on five slopes whose counts are multiplied."
+};
+
+pub struct Map {
+    _items: Grid<char>
 }
 
-impl <'a> Map <'a> {
-    pub fn new(_input: &'a Vec<String>) -> Self {
-        Self {
-            _items: _input
+impl Map {
+    pub fn new(_input: Vec<String>) -> Result<Self, PuzzleError> {
+        let _items = Grid::parse(&_input, |_c| match _c {
+            '.' | '#' => Ok(_c),
+            _ => Err(format!("unexpected '{}', expected '.' or '#'", _c))
+        })?;
+
+        if !_input[0].contains('.') {
+            return Err(PuzzleError::parse(&_input[0], "no open square in first row").at_line(1));
         }
+
+        Ok(Self {
+            _items
+        })
     }
 
     pub fn travel_and_count_trees(&self, _x_move: usize, _y_move: usize) -> u64 { // move is always from top-left to bottom-right
         let mut _trees: u64 = 0;
 
-        let mut _x_pos: usize = self._items.get(0).unwrap().find('.').unwrap();
+        let mut _x_pos: usize = (0..self._items.width()).find(|_x| self._items[(0, *_x)] == '.').unwrap();
         let mut _y_pos: usize = 0; // always 1st layer
 
-        let _layer_width = self._items.get(0).unwrap().len();
-        let _layers = self._items.len();
-
-        loop {
-            if _y_pos >= self._items.len() {
-                break;
-            }
-
-            let _layer = self._items.get(_y_pos).unwrap();
-
-            let _val = _layer.chars().nth(_x_pos).unwrap();
-
-            if _val == '#' {
+        while _y_pos < self._items.height() {
+            // the map repeats to the right
+            if *self._items.get_wrapping(_y_pos, _x_pos) == '#' {
                 _trees += 1;
             }
 
             _x_pos += _x_move;
             _y_pos += _y_move;
-
-            if _x_pos > _layer_width - 1 {
-                _x_pos = _x_pos - _layer_width;
-            }
         }
 
         _trees
@@ -49,18 +53,20 @@ pub struct Puzzle {}
 
 impl Solution for Puzzle {
     type PuzzleInput = String;
+    type Parsed = Map;
     type OutputPartOne = u64;
     type OutputPartTwo = u64;
 
-    fn solve_part_one(_input: &Vec<Self::PuzzleInput>) -> Self::OutputPartOne {
-        let _map = Map::new(_input);
-        _map.travel_and_count_trees(3, 1)
+    fn parse(_input: Vec<Self::PuzzleInput>) -> Result<Self::Parsed, PuzzleError> {
+        Map::new(_input)
     }
 
-    fn solve_part_two(_input: &Vec<Self::PuzzleInput>) -> Self::OutputPartTwo {
-        let _map = Map::new(_input);
+    fn solve_part_one(_input: &Self::Parsed) -> Result<Self::OutputPartOne, PuzzleError> {
+        Ok(_input.travel_and_count_trees(3, 1))
+    }
 
-        vec![
+    fn solve_part_two(_input: &Self::Parsed) -> Result<Self::OutputPartTwo, PuzzleError> {
+        Ok([
             (1,1),
             (3,1),
             (5,1),
@@ -69,61 +75,8 @@ impl Solution for Puzzle {
         ]
         .iter()
         .fold(1, |_acc, _case| {
-            let _res = _map.travel_and_count_trees(_case.0, _case.1);
+            let _res = _input.travel_and_count_trees(_case.0, _case.1);
             _acc * _res
-        })
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::puzzles::day_03::*;
-
-    #[test]
-    fn test_part_one() {
-        // given
-        let _input = vec![
-            "..##.......",
-            "#...#...#..",
-            ".#....#..#.",
-            "..#.#...#.#",
-            ".#...##..#.",
-            "..#.##.....",
-            ".#.#.#....#",
-            ".#........#",
-            "#.##...#...",
-            "#...##....#",
-            ".#..#...#.#",
-        ].into_iter().map(|_i| String::from(_i)).collect();
-
-        // when
-        let _res: u64 = Puzzle::solve_part_one(&_input);
-
-        // then
-        assert_eq!(_res, 7);
-    }
-
-    #[test]
-    fn test_part_two() {
-        // given
-        let _input = vec![
-            "..##.......",
-            "#...#...#..",
-            ".#....#..#.",
-            "..#.#...#.#",
-            ".#...##..#.",
-            "..#.##.....",
-            ".#.#.#....#",
-            ".#........#",
-            "#.##...#...",
-            "#...##....#",
-            ".#..#...#.#",
-        ].into_iter().map(|_i| String::from(_i)).collect();
-
-        // when
-        let _res: u64 = Puzzle::solve_part_two(&_input);
-
-        // then
-        assert_eq!(_res, 336);
+        }))
     }
 }
\ No newline at end of file
@@ -1,17 +1,20 @@
-use crate::puzzles::solution::Solution;
-
-#[derive(Debug, Copy, Clone)]
-struct Seat {
+use crate::error::PuzzleError;
+use crate::puzzles::grid::Grid;
+use crate::puzzles::metadata::{Metadata, Tag};
+use crate::puzzles::solution::{Solution, parse_lines};
+
+pub const METADATA: Metadata = Metadata {
+    title: "Binary Boarding",
+    tags: &[Tag::Bits, Tag::Search],
+    summary: "Decode boarding passes that binary partition the rows and columns of a plane, find the highest seat ID and then the one free seat between occupied ones."
+};
+
+#[derive(Debug, Copy, Clone, Default)]
+pub struct Seat {
     _pos_in_row: u8,
     _id: u64
 }
 
-impl Default for Seat {
-    fn default() -> Self {
-        Self {_pos_in_row: 0, _id: 0}
-    }
-}
-
 impl Seat {
     pub fn new(_pos_in_row: u8) -> Self {
         Self {
@@ -23,60 +26,41 @@ impl Seat {
     pub fn set_id(&mut self, _row_pos: u8, _col_pos: u8) {
         self._id = _row_pos as u64 * 8 + _col_pos as u64;
     }
-}
 
-#[derive(Debug, Copy, Clone)]
-struct Row { 
-    _num: u8,
-    _seats: [Seat; 8]
-}
-
-impl Default for Row {
-    fn default() -> Self {
-        Self {_num: 0, _seats: [Seat::default(); 8]}
+    pub fn describe(&self) -> String {
+        format!("row {}, column {}, seat ID {}", self._id / SEATS_IN_ROW as u64, self._pos_in_row, self._id)
     }
 }
 
-impl Row {
-    pub fn new(_num: u8) -> Self {
-        let mut _seats: [Seat; 8] = [Seat::default(); 8];
-        for _i in 0..8 {
-            _seats[_i] = Seat::new(_i as u8);
-        }
-        Self {
-            _num,
-            _seats
-        }
-    }
+const ROWS: usize = 128;
+const SEATS_IN_ROW: usize = 8;
 
-    pub fn add_occupied_seat(&mut self, _seat: &Seat) {
-        self._seats[_seat._pos_in_row as usize] = *_seat;
-    }
+#[derive(Debug, Clone)]
+pub struct Plane {
+    _seats: Grid<Seat>,
+    _passengers: Vec<Seat>
 }
 
-#[derive(Debug)]
-struct Plane {
-    _seats_rows: [Row; 128]
+impl Default for Plane {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Plane {
     pub fn new() -> Self {
-        let mut _seats_rows: [Row; 128] = [Row::default(); 128];
-        for _i in 0..128 {
-            _seats_rows[_i] = Row::new(_i as u8);
-        }
-
         Self {
-            _seats_rows
+            _seats: Grid::from_fn(SEATS_IN_ROW, ROWS, |_, _col| Seat::new(_col as u8)),
+            _passengers: vec![]
         }
     }
 
-    fn evaluate_pos_binary(_desc: &str) -> u8 {
+    fn evaluate_pos_binary(_desc: &str) -> Result<u8, PuzzleError> {
         let mut _range = 0..2u8.pow(_desc.len() as u32);
         let mut _ans: u8 = 0;
 
         let mut _pos = 0;
-        while _pos <= _desc.len() - 1 {
+        while _pos < _desc.len() {
             match &_desc[_pos.._pos+1] {
                 "R" | "B"=> {
                     _range.start = (_range.start + _range.end) / 2;
@@ -86,42 +70,48 @@ impl Plane {
                     _range.end = (_range.start + _range.end) / 2;
                     _ans = _range.start;
                 },
-                _ => panic!("Invalid value position evaluation")
+                _ => return Err(PuzzleError::parse(_desc, "expected only 'F', 'B', 'L' or 'R'"))
             }
 
             _pos += 1;
         }
 
-        _ans as u8
+        Ok(_ans)
     }
 
     pub fn find_free_seat(&mut self) -> Option<&Seat> {
-        let mut _last_empty_row_num: u8 = 0;
-        for _each_row in self._seats_rows[1..128].iter_mut() {
-            if let Some(_empty_seat) = _each_row._seats.iter_mut().find(|_s| _s._id == 0) {
+        let mut _last_empty_row_num: usize = 0;
+        for _row_num in 1..self._seats.height() {
+            if let Some(_col) = (0..self._seats.width()).find(|_col| self._seats[(_row_num, *_col)]._id == 0) {
 
-                if _each_row._num - _last_empty_row_num == 1 { // greedy, ignoring seats from plane's end
-                    _last_empty_row_num = _each_row._num;
+                if _row_num - _last_empty_row_num == 1 { // greedy, ignoring seats from plane's end
+                    _last_empty_row_num = _row_num;
                     continue;
                 }
 
-                _empty_seat.set_id(_each_row._num, _empty_seat._pos_in_row);
+                let _empty_seat = &mut self._seats[(_row_num, _col)];
+                _empty_seat.set_id(_row_num as u8, _empty_seat._pos_in_row);
                 return Some(_empty_seat)
             }
         }
         None
     }
 
-    pub fn add_passenger(&mut self, _boarding_pass: &str) -> Seat {
-        let _row_pos = Self::evaluate_pos_binary(&_boarding_pass[..7]);
-        let _col_pos = Self::evaluate_pos_binary(&_boarding_pass[_boarding_pass.len() - 3..]);
+    pub fn add_passenger(&mut self, _boarding_pass: &str) -> Result<Seat, PuzzleError> {
+        if _boarding_pass.len() != 10 || !_boarding_pass.is_ascii() {
+            return Err(PuzzleError::parse(_boarding_pass, "expected 10 characters boarding pass"));
+        }
+
+        let _row_pos = Self::evaluate_pos_binary(&_boarding_pass[..7])?;
+        let _col_pos = Self::evaluate_pos_binary(&_boarding_pass[_boarding_pass.len() - 3..])?;
 
         let mut _passenger_seat = Seat::new(_col_pos);
         _passenger_seat.set_id(_row_pos, _col_pos);
 
-        self._seats_rows[_row_pos as usize].add_occupied_seat(&_passenger_seat);
+        self._seats[(_row_pos as usize, _col_pos as usize)] = _passenger_seat;
+        self._passengers.push(_passenger_seat);
 
-        _passenger_seat
+        Ok(_passenger_seat)
     }
 }
 
@@ -129,26 +119,48 @@ pub struct Puzzle {}
 
 impl Solution for Puzzle {
     type PuzzleInput = String;
+    type Parsed = Plane;
     type OutputPartOne = u64;
     type OutputPartTwo = u64;
 
-    fn solve_part_one(_input: &Vec<Self::PuzzleInput>) -> Self::OutputPartOne {
+    const COMMANDS: &'static [(&'static str, &'static str)] = &[
+        ("seat [PASS]", "row, column and seat ID of PASS, or of every boarding pass typed so far")
+    ];
+
+    fn parse(_input: Vec<Self::PuzzleInput>) -> Result<Self::Parsed, PuzzleError> {
         let mut _plane = Plane::new();
+        parse_lines(&_input, |_i| _plane.add_passenger(_i))?;
+        Ok(_plane)
+    }
 
-        _input
+    fn solve_part_one(_input: &Self::Parsed) -> Result<Self::OutputPartOne, PuzzleError> {
+        _input._passengers
             .iter()
-            .map(|_i| _plane.add_passenger(_i))
-            .max_by_key(|_s| _s._id).unwrap()._id
+            .map(|_s| _s._id)
+            .max()
+            .ok_or_else(|| PuzzleError::solve("no boarding passes"))
     }
 
-    fn solve_part_two(_input: &Vec<Self::PuzzleInput>) -> Self::OutputPartTwo {
-        let mut _plane = Plane::new();
+    fn solve_part_two(_input: &Self::Parsed) -> Result<Self::OutputPartTwo, PuzzleError> {
+        // finding the seat marks it as taken, so work on a copy
+        let mut _plane = _input.clone();
 
-        for _each_i in _input {
-            _plane.add_passenger(_each_i);
-        }
+        _plane.find_free_seat()
+            .map(|_s| _s._id)
+            .ok_or_else(|| PuzzleError::solve("no free seat between occupied ones"))
+    }
 
-       _plane.find_free_seat().unwrap()._id
+    fn command(_input: &Self::Parsed, _name: &str, _args: &[&str]) -> Result<String, PuzzleError> {
+        match (_name, _args) {
+            ("seat", [_pass]) => Ok(Plane::new().add_passenger(_pass)?.describe()),
+            ("seat", []) => Ok(_input._passengers
+                .iter()
+                .enumerate()
+                .map(|(_i, _seat)| format!("line {}: {}", _i + 1, _seat.describe()))
+                .collect::<Vec<String>>()
+                .join("\n")),
+            _ => Err(PuzzleError::solve(format!("expected ':{}'", Self::COMMANDS[0].0)))
+        }
     }
 }
 
@@ -157,18 +169,16 @@ mod tests {
     use crate::puzzles::day_05::*;
 
     #[test]
-    fn test_part_one() {
+    fn test_seat_command() {
         // given
-        let _input = vec![
-            "BFFFBBFRRR",
-            "FFFBBBFRRR",
-            "BBFFBBFRLL"
-        ].into_iter().map(|_i| String::from(_i)).collect();
+        let _input = Puzzle::parse(vec!["BFFFBBFRRR".to_string(), "FFFBBBFRRR".to_string()]).unwrap();
 
         // when
-        let _res: u64 = Puzzle::solve_part_one(&_input);
+        let _res_pass = Puzzle::command(&_input, "seat", &["FBFBBFFRLR"]);
+        let _res_buffer = Puzzle::command(&_input, "seat", &[]);
 
         // then
-        assert_eq!(_res, 820);
+        assert_eq!(_res_pass.unwrap(), "row 44, column 5, seat ID 357");
+        assert_eq!(_res_buffer.unwrap(), "line 1: row 70, column 7, seat ID 567\nline 2: row 14, column 7, seat ID 119");
     }
 }
\ No newline at end of file
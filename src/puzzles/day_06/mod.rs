@@ -1,9 +1,17 @@
 use std::collections::HashMap;
 
-use crate::puzzles::solution::Solution;
+use crate::error::PuzzleError;
+use crate::puzzles::metadata::{Metadata, Tag};
+use crate::puzzles::solution::{Solution, parse_records};
+
+pub const METADATA: Metadata = Metadata {
+    title: "Custom Customs",
+    tags: &[Tag::Sets, Tag::Parsing],
+    summary: "Sum the questions anyone in each group of passengers answered yes to, then the questions everyone in the group answered yes to."
+};
 
 #[derive(Debug)]
-struct Passenger {
+pub struct Passenger {
     _answers: String
 }
 
@@ -16,17 +24,14 @@ impl Passenger {
 }
 
 #[derive(Debug)]
-struct Group {
+pub struct Group {
     _participants: Vec<Passenger>
 }
 
 impl Group {
-    pub fn new(_group_answers: &str) -> Self {
-        let mut _participants_answers: Vec<&str> = _group_answers.split(' ').collect();
-        let _ = _participants_answers.pop();
-
-        let _participants: Vec<Passenger> = _participants_answers.into_iter()
-            .map(|_a| Passenger::new(_a.to_string()))
+    pub fn new(_group_answers: &[String]) -> Self {
+        let _participants: Vec<Passenger> = _group_answers.iter()
+            .map(|_a| Passenger::new(_a.clone()))
             .collect();
 
         Self {
@@ -71,102 +76,23 @@ pub struct Puzzle {}
 
 impl Solution for Puzzle {
     type PuzzleInput = String;
+    type Parsed = Vec<Group>;
     type OutputPartOne = u64;
     type OutputPartTwo = u64;
 
-    fn normalize_input(_input: Vec<Self::PuzzleInput>) -> Vec<Self::PuzzleInput> {
-        let mut _normalized_input = vec![];
-
-        let mut _line_normalized: String = "".to_string();
-        for _line in _input {
-            if _line != "" {
-                _line_normalized.push_str(&_line);
-                _line_normalized.push_str(" ");
-            } else {
-                _normalized_input.push(_line_normalized);
-                _line_normalized = "".to_string();
-            }
-        }
-
-        _normalized_input.push(_line_normalized); // last line
-
-        _normalized_input
-    }    
-
-    fn solve_part_one(_input: &Vec<Self::PuzzleInput>) -> Self::OutputPartOne {
-        let mut _result: Self::OutputPartOne = 0;
-
-        _input.iter()
-            .map(|_q| Group::new(_q).get_num_of_all_yes_questions())
-            .fold(0, |acc, num| acc + num as u64)
-    }
-
-    fn solve_part_two(_input: &Vec<Self::PuzzleInput>) -> Self::OutputPartTwo {
-        let mut _result: Self::OutputPartTwo = 0;
-
-        _input.iter()
-            .map(|_q| Group::new(_q).get_num_of_same_yes_questions())
-            .fold(0, |acc, num| acc + num as u64)
+    fn parse(_input: Vec<Self::PuzzleInput>) -> Result<Self::Parsed, PuzzleError> {
+        parse_records(&_input, |_group| Ok(Group::new(_group)))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::puzzles::day_06::*;
-
-    #[test]
-    fn test_part_one() {
-        // given
-        let _input = vec![
-            "abc",
-            "",
-            "a",
-            "b",
-            "c",
-            "",
-            "ab",
-            "ac",
-            "",
-            "a",
-            "a",
-            "a",
-            "a",
-            "",
-            "b"
-        ].into_iter().map(|_i| String::from(_i)).collect();
-
-        // when
-        let _res: u64 = Puzzle::solve_part_one(&Puzzle::normalize_input(_input));
-
-        // then
-        assert_eq!(_res, 11);
+    fn solve_part_one(_input: &Self::Parsed) -> Result<Self::OutputPartOne, PuzzleError> {
+        Ok(_input.iter()
+            .map(|_g| _g.get_num_of_all_yes_questions())
+            .fold(0, |acc, num| acc + num as u64))
     }
 
-    #[test]
-    fn test_part_two() {
-        // given
-        let _input = vec![
-            "abc",
-            "",
-            "a",
-            "b",
-            "c",
-            "",
-            "ab",
-            "ac",
-            "",
-            "a",
-            "a",
-            "a",
-            "a",
-            "",
-            "b"
-        ].into_iter().map(|_i| String::from(_i)).collect();
-
-        // when
-        let _res: u64 = Puzzle::solve_part_two(&Puzzle::normalize_input(_input));
-
-        // then
-        assert_eq!(_res, 6);
+    fn solve_part_two(_input: &Self::Parsed) -> Result<Self::OutputPartTwo, PuzzleError> {
+        Ok(_input.iter()
+            .map(|_g| _g.get_num_of_same_yes_questions())
+            .fold(0, |acc, num| acc + num as u64))
     }
 }
\ No newline at end of file
@@ -1,55 +1,60 @@
 use std::collections::HashMap;
 
-use crate::puzzles::solution::Solution;
+use crate::error::PuzzleError;
+use crate::puzzles::metadata::{Metadata, Tag};
+use crate::puzzles::solution::{Solution, parse_lines};
 
 use regex::Regex;
 
+pub const METADATA: Metadata = Metadata {
+    title: "Handy Haversacks",
+    tags: &[Tag::Graph, Tag::Parsing],
+    summary: "Follow rules of which bags must contain which other bags to count the colours that can eventually hold a shiny gold bag and the bags a shiny gold bag holds."
+};
+
 lazy_static! {
     static ref BAGS_QUANTITY_REG: Regex = Regex::new(r"(\d+)\s+(.*)\s+bags*").unwrap();
 }
 
-type Bag = String;
-type Quantity = u64;
-type Bags = HashMap<Bag, HashMap<Bag, Quantity>>;
+pub type Bag = String;
+pub type Quantity = u64;
+pub type Bags = HashMap<Bag, HashMap<Bag, Quantity>>;
 
-fn parse_bag<'a>(_input: &'a str) -> (Bag, HashMap<Bag, Quantity>) {
-    let mut _bag: Bag= "".to_string();
+fn parse_bag(_input: &str) -> Result<(Bag, HashMap<Bag, Quantity>), PuzzleError> {
     let mut _inner: HashMap<Bag, Quantity> = HashMap::new();
 
-    let mut _i: usize = 0;
-    loop {
-        if _i >= _input.len() {
-            break;
-        } else if _input[_i..].starts_with("bags contain") {
-            _bag = _input[.._i].trim_end().to_string();
-            _i = _i + "bags contain ".len(); // shifting to rest of input
-            break;
-        }
-        _i += 1;
-    }
+    let _i = _input.find("bags contain ")
+        .ok_or_else(|| PuzzleError::parse(_input, "expected '<color> bags contain <contents>.'"))?;
 
-    let _rest = &_input[_i.._input.len()-1]; // removing . at the end
+    let _bag: Bag = _input[.._i].trim_end().to_string();
+    let _rest = _input[_i + "bags contain ".len()..] // shifting to rest of input
+        .strip_suffix('.')
+        .ok_or_else(|| PuzzleError::parse(_input, "expected rule to end with '.'"))?;
 
     if _rest != "no other bags" {
-        let mut _inner_bags_desc: Vec<&str> = _rest.split(',').map(|_s| _s.trim()).collect();
-        _inner_bags_desc.into_iter().for_each(|_d| {
-            let _matches = BAGS_QUANTITY_REG.captures(_d).unwrap();
+        for _d in _rest.split(',').map(|_s| _s.trim()) {
+            let _matches = BAGS_QUANTITY_REG.captures(_d)
+                .ok_or_else(|| PuzzleError::parse(_input, format!("expected '<quantity> <color> bags', found '{}'", _d)))?;
 
-            let _quantity  = _matches.get(1).unwrap().as_str();
-            let _name = _matches.get(2).unwrap().as_str();
+            let _quantity = _matches[1].parse::<Quantity>().map_err(|e| PuzzleError::parse(_input, e))?;
+            let _name = &_matches[2];
 
-            _inner.insert(_name.to_string(), _quantity.parse::<Quantity>().unwrap());
-        });
+            _inner.insert(_name.to_string(), _quantity);
+        }
     }
 
-    (_bag, _inner)
+    Ok((_bag, _inner))
+}
+
+pub fn parse_bags(_input: &[String]) -> Result<Bags, PuzzleError> {
+    Ok(parse_lines(_input, parse_bag)?.into_iter().collect())
 }
 
 fn contains_bag(_bags: &HashMap<Bag, HashMap<Bag, Quantity>>, _outer_bag: &Bag, _bag_to_look_for: &Bag) -> bool {
     _bags
         .get(_outer_bag)
-        .unwrap()
-        .iter()
+        .into_iter()
+        .flatten()
         .any(|(_inner_bag, _)| _inner_bag == _bag_to_look_for || contains_bag(_bags, _inner_bag, _bag_to_look_for))
 }
 
@@ -60,13 +65,13 @@ fn count_outer_bags(_bags: &HashMap<Bag, HashMap<Bag, Quantity>>, _bag_to_look_f
         .count()
 }
 
-fn count_nested_bags(_bags: &HashMap<Bag, HashMap<Bag, Quantity>>, _bag_to_look_for: &Bag) -> Quantity {
+fn count_nested_bags(_bags: &HashMap<Bag, HashMap<Bag, Quantity>>, _bag_to_look_for: &Bag) -> Result<Quantity, PuzzleError> {
     _bags
         .get(_bag_to_look_for)
-        .unwrap()
+        .ok_or_else(|| PuzzleError::solve(format!("no rule for '{}' bags", _bag_to_look_for)))?
         .iter()
-        .fold(0, |_acc, (_inner_bag, _inner_bag_quantity)| {
-            _acc + *_inner_bag_quantity + *_inner_bag_quantity * count_nested_bags(_bags, _inner_bag)
+        .try_fold(0, |_acc, (_inner_bag, _inner_bag_quantity)| {
+            Ok(_acc + *_inner_bag_quantity + *_inner_bag_quantity * count_nested_bags(_bags, _inner_bag)?)
         })
 }
 
@@ -74,29 +79,43 @@ pub struct Puzzle {}
 
 impl Solution for Puzzle {
     type PuzzleInput = Bag;
+    type Parsed = Bags;
     type OutputPartOne = usize;
     type OutputPartTwo = u64;
 
-    fn solve_part_one(_input: &Vec<Self::PuzzleInput>) -> Self::OutputPartOne {
-        let mut _bags: Bags = HashMap::new();
+    const COMMANDS: &'static [(&'static str, &'static str)] = &[
+        ("holders COLOR", "bags that eventually contain a COLOR bag"),
+        ("contents COLOR", "number of bags inside a COLOR bag")
+    ];
 
-        for _each_bag_desc in _input {
-            let (_bag, _inner_bags) = parse_bag(_each_bag_desc);
-            _bags.insert(_bag, _inner_bags);
-        }
+    fn parse(_input: Vec<Self::PuzzleInput>) -> Result<Self::Parsed, PuzzleError> {
+        parse_bags(&_input)
+    }
 
-        count_outer_bags(&_bags, &"shiny gold".to_string())
+    fn solve_part_one(_input: &Self::Parsed) -> Result<Self::OutputPartOne, PuzzleError> {
+        Ok(count_outer_bags(_input, &"shiny gold".to_string()))
     }
 
-    fn solve_part_two(_input: &Vec<Self::PuzzleInput>) -> Self::OutputPartTwo {
-        let mut _bags: Bags = HashMap::new();
+    fn solve_part_two(_input: &Self::Parsed) -> Result<Self::OutputPartTwo, PuzzleError> {
+        count_nested_bags(_input, &"shiny gold".to_string())
+    }
 
-        for _each_bag_desc in _input {
-            let (_bag, _inner_bags) = parse_bag(_each_bag_desc);
-            _bags.insert(_bag, _inner_bags);
+    fn command(_input: &Self::Parsed, _name: &str, _args: &[&str]) -> Result<String, PuzzleError> {
+        let _color: Bag = _args.join(" ");
+        if _color.is_empty() {
+            return Err(PuzzleError::solve(format!("':{}' needs a bag color, e.g. shiny gold", _name)));
         }
 
-        count_nested_bags(&_bags, &"shiny gold".to_string())
+        match _name {
+            "holders" => {
+                let mut _holders: Vec<&Bag> = _input.keys().filter(|_b| contains_bag(_input, _b, &_color)).collect();
+                _holders.sort();
+                let _names: Vec<&str> = _holders.iter().map(|_b| _b.as_str()).collect();
+                Ok(format!("{} bags can contain {}: {}", _holders.len(), _color, _names.join(", ")))
+            },
+            "contents" => Ok(format!("{} holds {} bags", _color, count_nested_bags(_input, &_color)?)),
+            _ => Err(PuzzleError::solve(format!("':{}' is not a command of day_07", _name)))
+        }
     }
 }
 
@@ -105,58 +124,24 @@ mod tests {
     use crate::puzzles::day_07::*;
 
     #[test]
-    fn test_part_one() {
-        // given
-        let _input = vec![
-            "light red bags contain 1 bright white bag, 2 muted yellow bags.",
-            "dark orange bags contain 3 bright white bags, 4 muted yellow bags.",
-            "bright white bags contain 1 shiny gold bag.",
-            "muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.",
-            "shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.",
-            "dark olive bags contain 3 faded blue bags, 4 dotted black bags.",
-            "vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.",
-            "faded blue bags contain no other bags.",
-            "dotted black bags contain no other bags."
-        ].into_iter().map(|_i| String::from(_i)).collect();
-
-        // when
-        let _res: usize = Puzzle::solve_part_one(&_input);
-
-        // then
-        assert_eq!(_res, 4);
-    }
-
-    #[test]
-    fn test_part_two() {
+    fn test_commands() {
         // given
-        let _input_1 = vec![
-            "light red bags contain 1 bright white bag, 2 muted yellow bags.",
-            "dark orange bags contain 3 bright white bags, 4 muted yellow bags.",
-            "bright white bags contain 1 shiny gold bag.",
-            "muted yellow bags contain 2 shiny gold bags, 9 faded blue bags.",
-            "shiny gold bags contain 1 dark olive bag, 2 vibrant plum bags.",
-            "dark olive bags contain 3 faded blue bags, 4 dotted black bags.",
-            "vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.",
-            "faded blue bags contain no other bags.",
-            "dotted black bags contain no other bags."
-        ].into_iter().map(|_i| String::from(_i)).collect();
-
-        let _input_2 = vec![
-            "shiny gold bags contain 2 dark red bags.",
-            "dark red bags contain 2 dark orange bags.",
-            "dark orange bags contain 2 dark yellow bags.",
-            "dark yellow bags contain 2 dark green bags.",
-            "dark green bags contain 2 dark blue bags.",
-            "dark blue bags contain 2 dark violet bags.",
-            "dark violet bags contain no other bags."
-        ].into_iter().map(|_i| String::from(_i)).collect();
+        let _input = parse_bags(&[
+            "light red bags contain 1 bright white bag, 2 muted yellow bags.".to_string(),
+            "bright white bags contain 1 shiny gold bag.".to_string(),
+            "muted yellow bags contain 2 shiny gold bags.".to_string(),
+            "shiny gold bags contain 3 dark olive bags.".to_string(),
+            "dark olive bags contain no other bags.".to_string()
+        ]).unwrap();
 
         // when
-        let _res_1:u64 = Puzzle::solve_part_two(&_input_1);
-        let _res_2:u64 = Puzzle::solve_part_two(&_input_2);
+        let _res_holders = Puzzle::command(&_input, "holders", &["shiny", "gold"]);
+        let _res_contents = Puzzle::command(&_input, "contents", &["light", "red"]);
+        let _res_missing = Puzzle::command(&_input, "contents", &[]);
 
         // then
-        assert_eq!(_res_1, 32);
-        assert_eq!(_res_2, 126);
+        assert_eq!(_res_holders.unwrap(), "3 bags can contain shiny gold: bright white, light red, muted yellow");
+        assert_eq!(_res_contents.unwrap(), "light red holds 23 bags");
+        assert!(_res_missing.is_err());
     }
 }
\ No newline at end of file
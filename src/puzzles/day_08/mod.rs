@@ -1,52 +1,74 @@
-use crate::puzzles::solution::Solution;
+use crate::error::PuzzleError;
+use crate::puzzles::lint::Diagnostic;
+use crate::puzzles::metadata::{Metadata, Tag};
+use crate::puzzles::solution::{Solution, parse_lines};
+
+pub const METADATA: Metadata = Metadata {
+    title: "Handheld Halting",
+    tags: &[Tag::Simulation, Tag::Parsing],
+    summary: "Run a boot code program of acc, jmp and nop instructions until it would loop, then repair it by swapping the one jmp or nop that lets it terminate."
+};
+
+const INSTRUCTION_FORM: &str = "'<operation> <argument>' like 'acc +1'";
 
 #[derive(Debug, Copy, Clone)]
-enum Instruction {
-    NOP(i32),
-    ACC(i32),
-    JMP(i32)
+pub enum Instruction {
+    Nop(i32),
+    Acc(i32),
+    Jmp(i32)
 }
 
 impl Instruction {
     fn is_nop_or_jmp(&self) -> bool {
-        match *self {
-            Instruction::JMP(_) | Instruction::NOP(_) => true,
-            _ => false
+        matches!(*self, Instruction::Jmp(_) | Instruction::Nop(_))
+    }
+}
+
+impl std::fmt::Display for Instruction {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Instruction::Nop(val) => write!(f, "nop {:+}", val),
+            Instruction::Acc(val) => write!(f, "acc {:+}", val),
+            Instruction::Jmp(val) => write!(f, "jmp {:+}", val)
         }
     }
 }
 
-struct Console {
+#[derive(Clone)]
+pub struct Console {
     instructions: Vec<Instruction>,
     pub acc: i32,
     last_operations_swapped_position: usize,
 }
 
 impl Console {
-    pub fn new(instructions: &Vec<String>) -> Self {
-        Self {
-            instructions: instructions.iter().map(|i| Self::parse_instruction(i)).collect(),
+    pub fn new(instructions: &[String]) -> Result<Self, PuzzleError> {
+        Ok(Self {
+            instructions: parse_lines(instructions, Self::parse_instruction)?,
             acc: 0,
             last_operations_swapped_position: 0,
-        }
+        })
     }
 
-    fn parse_instruction(instruction: &str) -> Instruction {
+    fn parse_instruction(instruction: &str) -> Result<Instruction, PuzzleError> {
         let instr_data: Vec<&str> = instruction.split(' ').collect();
-        let instr_name: &str = instr_data.get(0).unwrap();
-        let instr_value: &str = instr_data.get(1).unwrap();
+        let (instr_name, instr_value) = match instr_data[..] {
+            [name, value] => (name, value),
+            _ => return Err(PuzzleError::parse(instruction, "expected '<operation> <argument>'"))
+        };
         let sign_modifier: i8 =  match &instr_value[..0] {
             "-" => -1,
             _ => 1
         };
 
-        let instr_value = sign_modifier as i32 * instr_value.parse::<i32>().unwrap();
+        let instr_value = sign_modifier as i32 * instr_value.parse::<i32>()
+            .map_err(|e| PuzzleError::parse(instruction, e))?;
 
         match instr_name {
-            "nop" => Instruction::NOP(instr_value),
-            "acc" => Instruction::ACC(instr_value),
-            "jmp" => Instruction::JMP(instr_value),
-            _ => panic!(format!("Unknown command found {}", instr_name))
+            "nop" => Ok(Instruction::Nop(instr_value)),
+            "acc" => Ok(Instruction::Acc(instr_value)),
+            "jmp" => Ok(Instruction::Jmp(instr_value)),
+            _ => Err(PuzzleError::parse(instruction, format!("unknown operation '{}'", instr_name)))
         }
     }
 
@@ -54,15 +76,15 @@ impl Console {
         let instr = self.instructions.get(pos).unwrap();
 
         let swapped = match instr {
-            Instruction::NOP(val) => Instruction::JMP(*val),
-            Instruction::JMP(val) => Instruction::NOP(*val),
+            Instruction::Nop(val) => Instruction::Jmp(*val),
+            Instruction::Jmp(val) => Instruction::Nop(*val),
             _ => *instr
         };
 
         self.instructions[pos] = swapped;
     }
     
-    pub fn swap_next(&mut self) {
+    pub fn swap_next(&mut self) -> Result<(), PuzzleError> {
         if self.last_operations_swapped_position != 0 {
             self.swap_nop_jmp(self.last_operations_swapped_position); // swapping back previous instruction
         }
@@ -71,11 +93,13 @@ impl Console {
             .iter()
             .enumerate()
             .position(|(i, instruction)| i > self.last_operations_swapped_position && instruction.is_nop_or_jmp())
-            .unwrap();
+            .ok_or_else(|| PuzzleError::solve("no single nop/jmp swap terminates the program"))?;
         
         self.swap_nop_jmp(next_pos_to_swap);
 
         self.last_operations_swapped_position = next_pos_to_swap;
+
+        Ok(())
     }
 
     pub fn detect_infinite_cycle(&mut self) -> bool {
@@ -84,12 +108,7 @@ impl Console {
         self.acc = 0;
 
         let mut contains_infinite_cycle = false;
-        loop {
-            let instruction = match self.instructions.get(position) {
-                Some(instruction) => instruction, 
-                None => { break; }
-            };
-
+        while let Some(instruction) = self.instructions.get(position) {
             if visited_positions.contains(&position) {
                 contains_infinite_cycle = true;
                 break;
@@ -98,14 +117,14 @@ impl Console {
             visited_positions.push(position);
 
             match instruction {
-                Instruction::NOP(_) => {
+                Instruction::Nop(_) => {
                     position += 1;
                 },
-                Instruction::ACC(val) => {
+                Instruction::Acc(val) => {
                     self.acc += val;
                     position += 1;
                 },
-                Instruction::JMP(val) => {
+                Instruction::Jmp(val) => {
                     position = (position as i32 + val) as usize;
                 }
             }
@@ -113,30 +132,104 @@ impl Console {
 
         contains_infinite_cycle 
     }
+
+    // every executed instruction with the accumulator after it, until the
+    // program ends or is about to run an instruction a second time
+    pub fn trace(&self) -> String {
+        let mut lines: Vec<String> = vec![];
+        let mut visited_positions: Vec<usize> = vec![];
+        let mut position: usize = 0;
+        let mut acc = 0;
+
+        while let Some(instruction) = self.instructions.get(position) {
+            if visited_positions.contains(&position) {
+                lines.push(format!("line {} runs again, acc {}", position + 1, acc));
+                return lines.join("\n");
+            }
+            visited_positions.push(position);
+
+            let line = position + 1;
+            match instruction {
+                Instruction::Nop(_) => position += 1,
+                Instruction::Acc(val) => {
+                    acc += val;
+                    position += 1;
+                },
+                Instruction::Jmp(val) => position = (position as i32 + val) as usize
+            }
+            lines.push(format!("line {:<4} {:<8} acc {}", line, instruction.to_string(), acc));
+        }
+
+        lines.push(format!("terminated, acc {}", acc));
+        lines.join("\n")
+    }
 }
 
 pub struct Puzzle {}
 
 impl Solution for Puzzle {
     type PuzzleInput = String;
+    type Parsed = Console;
     type OutputPartOne = i32;
     type OutputPartTwo = i32;
 
-    fn solve_part_one(input: &Vec<Self::PuzzleInput>) -> Self::OutputPartOne {
-        let mut console = Console::new(input);
+    const COMMANDS: &'static [(&'static str, &'static str)] = &[
+        ("trace", "run the program step by step until it ends or loops")
+    ];
+
+    fn parse(_input: Vec<Self::PuzzleInput>) -> Result<Self::Parsed, PuzzleError> {
+        Console::new(&_input)
+    }
+
+    fn validate(input: &[String]) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+
+        for (i, line) in input.iter().enumerate() {
+            let (operation, argument) = match line.split_once(' ') {
+                Some(instruction) => instruction,
+                None if line.is_empty() => {
+                    diagnostics.push(Diagnostic::end_of(i + 1, line, "empty line").expected(INSTRUCTION_FORM));
+                    continue;
+                },
+                None => {
+                    diagnostics.push(Diagnostic::end_of(i + 1, line, "missing argument").expected(INSTRUCTION_FORM));
+                    continue;
+                }
+            };
+
+            if !matches!(operation, "nop" | "acc" | "jmp") {
+                diagnostics.push(Diagnostic::token(i + 1, line, operation, format!("unknown operation '{}'", operation)).expected("nop, acc or jmp"));
+            }
+            if argument.parse::<i32>().is_err() {
+                diagnostics.push(Diagnostic::token(i + 1, line, argument, format!("invalid argument '{}'", argument)).expected("a signed number like +3 or -7"));
+            }
+        }
+
+        diagnostics
+    }
+
+    fn solve_part_one(input: &Self::Parsed) -> Result<Self::OutputPartOne, PuzzleError> {
+        let mut console = input.clone();
         console.detect_infinite_cycle();
 
-        console.acc
+        Ok(console.acc)
     }
 
-    fn solve_part_two(input: &Vec<Self::PuzzleInput>) -> Self::OutputPartTwo {
-        let mut console = Console::new(input);
+    fn solve_part_two(input: &Self::Parsed) -> Result<Self::OutputPartTwo, PuzzleError> {
+        let mut console = input.clone();
 
         while console.detect_infinite_cycle() {
-            console.swap_next();
+            console.swap_next()?;
         };
 
-        console.acc
+        Ok(console.acc)
+    }
+
+    fn command(input: &Self::Parsed, name: &str, _args: &[&str]) -> Result<String, PuzzleError> {
+        match name {
+            "trace" => Ok(input.trace()),
+            _ => Err(PuzzleError::solve(format!("':{}' is not a command of day_08", name)))
+        }
     }
 }
 
@@ -145,46 +238,61 @@ mod tests {
     use crate::puzzles::day_08::*;
 
     #[test]
-    fn test_part_one() {
+    fn test_trace() {
         // given
         let _input = vec![
             "nop +0",
             "acc +1",
-            "jmp +4",
-            "acc +3",
-            "jmp -3",
-            "acc -99",
-            "acc +1",
-            "jmp -4",
-            "acc +6"
-        ].into_iter().map(|_i| String::from(_i)).collect();
+            "jmp -2"
+        ].into_iter().map(String::from).collect::<Vec<String>>();
 
         // when
-        let _res: i32 = Puzzle::solve_part_one(&_input);
+        let _res = Puzzle::parse(_input).unwrap().trace();
 
         // then
-        assert_eq!(_res, 5);
+        assert_eq!(_res, "\
+line 1    nop +0   acc 0
+line 2    acc +1   acc 1
+line 3    jmp -2   acc 1
+line 1 runs again, acc 1");
     }
 
     #[test]
-    fn test_part_two() {
+    fn test_validate() {
         // given
         let _input = vec![
             "nop +0",
-            "acc +1",
-            "jmp +4",
-            "acc +3",
-            "jmp -3",
-            "acc -99",
-            "acc +1",
-            "jmp -4",
-            "acc +6"
-        ].into_iter().map(|_i| String::from(_i)).collect();
+            "mul +3",
+            "jmp",
+            "acc 1O"
+        ].into_iter().map(String::from).collect::<Vec<String>>();
+
+        // when
+        let _res: Vec<(Option<usize>, usize, usize, String)> = Puzzle::validate(&_input)
+            .into_iter()
+            .map(|_d| (_d.line, _d.column, _d.len, _d.message))
+            .collect();
+
+        // then
+        assert_eq!(_res, vec![
+            (Some(2), 1, 3, "unknown operation 'mul'".to_string()),
+            (Some(3), 4, 1, "missing argument".to_string()),
+            (Some(4), 5, 2, "invalid argument '1O'".to_string())
+        ]);
+    }
+
+    #[test]
+    fn test_unknown_operation() {
+        // given
+        let _input = vec![
+            "nop +0",
+            "mul +3"
+        ].into_iter().map(String::from).collect::<Vec<String>>();
 
         // when
-        let _res: i32 = Puzzle::solve_part_two(&_input);
+        let _res = Puzzle::parse(_input);
 
         // then
-        assert_eq!(_res, 8);
+        assert_eq!(_res.err().unwrap().to_string(), "line 2: invalid input, unknown operation 'mul' in 'mul +3'");
     }
-}
\ No newline at end of file
+}
@@ -1,13 +1,32 @@
+use crate::error::PuzzleError;
+use crate::puzzles::metadata::{Metadata, Tag};
 use crate::puzzles::solution::Solution;
 
-struct Xmas<'a> {
-    cipher: &'a Vec<i64>
+pub const METADATA: Metadata = Metadata {
+    title: "Encoding Error",
+    tags: &[Tag::Search],
+    summary: "Find the first number of the XMAS cipher that is not the sum of two of the numbers before it, then the contiguous range summing to it."
+};
+
+// the real input uses a 25 number preamble, examples override it with a
+// leading `preamble: N` line
+const DEFAULT_PREAMBLE_LEN: usize = 25;
+const PREAMBLE_PREFIX: &str = "preamble: ";
+
+pub struct Xmas {
+    cipher: Vec<i64>,
+    preamble_len: usize,
+    corrupted: Option<i64>
 }
 
-impl <'a> Xmas <'a> {
-    pub fn new(cipher: &'a Vec<i64>) -> Self {
+impl Xmas {
+    // the corrupted number is needed by both parts, so it is found up front
+    pub fn new(cipher: Vec<i64>, preamble_len: usize) -> Self {
+        let corrupted = Self::find_corrupted(&cipher, preamble_len);
         Self {
-            cipher
+            cipher,
+            preamble_len,
+            corrupted
         }
     }
 
@@ -27,12 +46,12 @@ impl <'a> Xmas <'a> {
         out
     }
 
-    pub fn find_corrupted(&self, preamble_len: usize) -> Option<i64> {
+    fn find_corrupted(cipher: &[i64], preamble_len: usize) -> Option<i64> {
         let mut corrupted: Option<i64> = None;
-        for i in 0..self.cipher.len() - preamble_len {
-            let preamble = &self.cipher[i..i + preamble_len];
-            let elem = self.cipher.get(i + preamble_len).unwrap();
-            if !Self::get_next_possible_values(&preamble).contains(&elem) {
+        for i in 0..cipher.len().saturating_sub(preamble_len) {
+            let preamble = &cipher[i..i + preamble_len];
+            let elem = cipher.get(i + preamble_len).unwrap();
+            if !Self::get_next_possible_values(preamble).contains(elem) {
                 corrupted = Some(*elem);
                 break;
             }
@@ -41,14 +60,14 @@ impl <'a> Xmas <'a> {
         corrupted
     }
 
-    pub fn find_range_summing_to_corrupted(&self, corrupted_val: i64) -> i64 {
+    pub fn find_range_summing_to_corrupted(&self, corrupted_val: i64) -> Option<i64> {
         let mut contiguous_set = vec![];
 
         'start_index_loop: for start_i in 0..self.cipher.len() {
             let mut sum: i64 = 0;
             contiguous_set = vec![];
 
-            for end_i in start_i + 1..self.cipher.len() - 1 {
+            for end_i in start_i + 1..self.cipher.len().saturating_sub(1) {
                 contiguous_set.push(&self.cipher[end_i]);
                 sum += self.cipher[end_i];
                 if sum == corrupted_val {
@@ -59,104 +78,47 @@ impl <'a> Xmas <'a> {
             }
         }
 
-        let min = *contiguous_set.iter().min().unwrap();
-        let max = *contiguous_set.iter().max().unwrap();
+        let min = *contiguous_set.iter().min()?;
+        let max = *contiguous_set.iter().max()?;
 
-        min + max
+        Some(min + max)
     }
 }
 
 pub struct Puzzle {}
 
 impl Solution for Puzzle {
-    type PuzzleInput = i64;
+    type PuzzleInput = String;
+    type Parsed = Xmas;
     type OutputPartOne = i64;
     type OutputPartTwo = i64;
 
-    fn solve_part_one(input: &Vec<Self::PuzzleInput>) -> Self::OutputPartOne {
-        let xmas = Xmas::new(input);
-        xmas.find_corrupted(25).unwrap()
-    }
+    fn parse(input: Vec<Self::PuzzleInput>) -> Result<Self::Parsed, PuzzleError> {
+        let (preamble_len, skip) = match input.first().and_then(|l| l.strip_prefix(PREAMBLE_PREFIX)) {
+            Some(len) => (len.parse::<usize>().map_err(|e| PuzzleError::parse(&input[0], e).at_line(1))?, 1),
+            None => (DEFAULT_PREAMBLE_LEN, 0)
+        };
+        if preamble_len < 2 {
+            return Err(PuzzleError::parse(&input[0], "preamble needs at least two numbers").at_line(1));
+        }
 
-    fn solve_part_two(input: &Vec<Self::PuzzleInput>) -> Self::OutputPartTwo {
-        let xmas = Xmas::new(input);
-        let corrupted_val = xmas.find_corrupted(25).unwrap();
-        xmas.find_range_summing_to_corrupted(corrupted_val)
+        let cipher = input.iter()
+            .enumerate()
+            .skip(skip)
+            .map(|(i, l)| l.parse::<i64>().map_err(|e| PuzzleError::parse(l, e).at_line(i + 1)))
+            .collect::<Result<Vec<i64>, PuzzleError>>()?;
+
+        Ok(Xmas::new(cipher, preamble_len))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::puzzles::day_09::*;
-
-    #[test]
-    fn test_part_one() {
-        // given
-        let input = vec![
-            35,
-            20,
-            15,
-            25,
-            47,
-            40,
-            62,
-            55,
-            65,
-            95,
-            102,
-            117,
-            150,
-            182,
-            127,
-            219,
-            299,
-            277,
-            309,
-            576
-        ];
-
-        let xmas = Xmas::new(&input);
-
-        // when
-        let res = xmas.find_corrupted(5).unwrap();
-
-        // then
-        assert_eq!(res, 127);
+    fn solve_part_one(input: &Self::Parsed) -> Result<Self::OutputPartOne, PuzzleError> {
+        input.corrupted
+            .ok_or_else(|| PuzzleError::solve(format!("every number is a sum of two of the previous {}", input.preamble_len)))
     }
 
-    #[test]
-    fn test_part_two() {
-        // given
-        let input = vec![
-            35,
-            20,
-            15,
-            25,
-            47,
-            40,
-            62,
-            55,
-            65,
-            95,
-            102,
-            117,
-            150,
-            182,
-            127,
-            219,
-            299,
-            277,
-            309,
-            576
-        ];
-
-        let xmas = Xmas::new(&input);
-        let corrupted_val = xmas.find_corrupted(5).unwrap();
-
-        // when
-        let res = xmas.find_range_summing_to_corrupted(corrupted_val);
-
-        // then
-        assert_eq!(res, 62);
+    fn solve_part_two(input: &Self::Parsed) -> Result<Self::OutputPartTwo, PuzzleError> {
+        let corrupted_val = Self::solve_part_one(input)?;
+        input.find_range_summing_to_corrupted(corrupted_val)
+            .ok_or_else(|| PuzzleError::solve(format!("no contiguous range sums to {}", corrupted_val)))
     }
 }
\ No newline at end of file
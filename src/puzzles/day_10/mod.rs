@@ -1,8 +1,16 @@
 use std::collections::HashMap;
 
+use crate::error::PuzzleError;
+use crate::puzzles::metadata::{Metadata, Tag};
 use crate::puzzles::solution::Solution;
 
-fn count_distinct(inp: &Vec<u32>, memory: &mut HashMap<usize, u64>, index:usize) -> u64 {
+pub const METADATA: Metadata = Metadata {
+    title: "Adapter Array",
+    tags: &[Tag::DynamicProgramming],
+    summary: "Chain every joltage adapter to multiply the counts of 1 and 3 jolt differences, then count all the ways the adapters can be arranged."
+};
+
+fn count_distinct(inp: &[u32], memory: &mut HashMap<usize, u64>, index:usize) -> u64 {
     if index == inp.len() - 1 {
         return 1;
     }
@@ -29,20 +37,25 @@ pub struct Puzzle {}
 
 impl Solution for Puzzle {
     type PuzzleInput = u32;
+    type Parsed = Vec<u32>;
     type OutputPartOne = u32;
     type OutputPartTwo = u64;
 
-    fn solve_part_one(input: &Vec<Self::PuzzleInput>) -> Self::OutputPartOne {
-        let mut sorted = input.clone();
+    fn parse(_input: Vec<Self::PuzzleInput>) -> Result<Self::Parsed, PuzzleError> {
+        // outlet and device joltage are part of the chain
+        let mut sorted = _input;
         sorted.push(0);
-        sorted.push(sorted.iter().max().unwrap() + 3); // pushing device joltage
+        sorted.push(sorted.iter().max().unwrap() + 3);
         sorted.sort();
 
+        Ok(sorted)
+    }
 
+    fn solve_part_one(input: &Self::Parsed) -> Result<Self::OutputPartOne, PuzzleError> {
         let mut last_out_joltage = 0;
         let mut differences_counter: HashMap<u32, u32> = HashMap::new();
 
-        for elem in &sorted {
+        for elem in input {
             let diff = elem - last_out_joltage;
             let counter = differences_counter.entry(diff).or_insert(0);
             *counter += 1;
@@ -50,111 +63,16 @@ impl Solution for Puzzle {
             last_out_joltage = *elem;
         }
 
-        differences_counter.get(&1).unwrap() * differences_counter.get(&3).unwrap()
-    }
-
-    fn solve_part_two(input: &Vec<Self::PuzzleInput>) -> Self::OutputPartTwo {
-        let mut sorted = input.clone();
-        sorted.push(0);
-        sorted.push(sorted.iter().max().unwrap() + 3); // pushing device joltage
-
-        sorted.sort();
-
-        let mut memory = HashMap::new();
+        if let Some(diff) = differences_counter.keys().find(|_d| **_d > 3) {
+            return Err(PuzzleError::solve(format!("adapters differ by {} jolts", diff)));
+        }
 
-        count_distinct(&sorted, &mut memory, 0)
+        Ok(differences_counter.get(&1).unwrap_or(&0) * differences_counter.get(&3).unwrap_or(&0))
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::puzzles::day_10::*;
-
-    #[test]
-    fn test_part_one() {
-        // given
-        let _input = vec![
-            28,
-            33,
-            18,
-            42,
-            31,
-            14,
-            46,
-            20,
-            48,
-            47,
-            24,
-            23,
-            49,
-            45,
-            19,
-            38,
-            39,
-            11,
-            1,
-            32,
-            25,
-            35,
-            8,
-            17,
-            7,
-            9,
-            4,
-            2,
-            34,
-            10,
-            3
-        ];
-
-        // when
-        let _res: u32 = Puzzle::solve_part_one(&_input);
-
-        // then
-        assert_eq!(_res, 220);
-    }
+    fn solve_part_two(input: &Self::Parsed) -> Result<Self::OutputPartTwo, PuzzleError> {
+        let mut memory = HashMap::new();
 
-    #[test]
-    fn test_part_two() {
-        // given
-        let _input = vec![
-            28,
-            33,
-            18,
-            42,
-            31,
-            14,
-            46,
-            20,
-            48,
-            47,
-            24,
-            23,
-            49,
-            45,
-            19,
-            38,
-            39,
-            11,
-            1,
-            32,
-            25,
-            35,
-            8,
-            17,
-            7,
-            9,
-            4,
-            2,
-            34,
-            10,
-            3
-        ];
-
-        // when
-        let _res: u64 = Puzzle::solve_part_two(&_input);
-
-        // then
-        assert_eq!(_res, 19208);
+        Ok(count_distinct(input, &mut memory, 0))
     }
 }
\ No newline at end of file
@@ -1,111 +1,70 @@
+use crate::error::PuzzleError;
+use crate::puzzles::grid::{Grid, ALL_AROUND};
+use crate::puzzles::metadata::{Metadata, Tag};
 use crate::puzzles::solution::Solution;
 
-struct Ferry {
-    seats: Vec<Vec<char>>,
+pub const METADATA: Metadata = Metadata {
+    title: "Seating System",
+    tags: &[Tag::Grid, Tag::Simulation],
+    summary: "Let passengers take and leave seats by their neighbours until the waiting area is stable, first looking at adjacent seats and then at the first seat in each direction."
+};
+
+pub struct Ferry {
+    seats: Grid<char>,
     pub seats_state_stable: bool
 }
 
 impl Ferry {
-    pub fn new(seats: Vec<Vec<char>>) -> Self {
+    pub fn new(seats: Grid<char>) -> Self {
         Self {
             seats,
             seats_state_stable: false
         }
     }
 
-    fn get_seats_copy(&self) -> Vec<Vec<char>> {
-        self.seats.clone()
-    }
-
-    fn get_neighbours(&self, x_pos: &usize, y_pos: &usize) -> Vec<(usize, usize, char)> {
-        let mut neighbours: Vec<(usize, usize, char)> = vec![];
-
-        let x_range = if *x_pos > 0 { x_pos - 1..x_pos + 2 } else { 0..2 };
-
-        for x in x_range {
-            match self.seats.get(x) {
-                Some(row) => {
-                    let y_range = if *y_pos > 0 { y_pos - 1..y_pos + 2 } else { 0..2 };
-
-                    for y in y_range {
-                        match row.get(y) {
-                            Some(seat) => {
-                                if *y_pos != y || *x_pos != x {
-                                    neighbours.push((*x_pos, *y_pos, *seat))
-                                }
-                            },
-                            _ => {}
-                        }
-                    }
-                },
-                _ => {}
-            };
-        }
-
-        neighbours
+    fn get_neighbours(&self, row: usize, col: usize) -> Vec<char> {
+        self.seats.neighbours(row, col, ALL_AROUND)
+            .map(|(_, _, seat)| *seat)
+            .collect()
     }
 
-    fn get_far_neighbours(&self, x_pos: &usize, y_pos: &usize) -> Vec<(usize, usize, char)> {
-        let mut neighbours: Vec<(usize, usize, char)> = vec![];
-
-        let x_incomes:Vec<isize> = if *x_pos > 0 { vec![-1, 0, 1] } else { vec![0, 1] };
-        let y_incomes:Vec<isize> = if *y_pos > 0 { vec![-1, 0, 1] } else { vec![0, 1] };
-
-        for a in &x_incomes {
-            for b in &y_incomes {
-                let mut x = (*x_pos as isize + a) as usize;
-                let mut y = (* y_pos as isize + b) as usize;
-
-                loop {
-                    if x > self.seats.len()-1 || y > self.seats.get(0).unwrap().len()-1 {
-                        break;
-                    }
-
-                    let n =  &self.seats[x][y];
-                    if *n != '.' {
-                        if *x_pos != x || *y_pos != y {
-                            neighbours.push((x, y, *n));
-                        }
-                        break;
-                    }
-
-                    x = (x as isize + a) as usize;
-                    y = (y as isize + b) as usize;
-                }
-            }
-        }
-
-        neighbours
+    // first seat seen in each direction, floor is looked over
+    fn get_far_neighbours(&self, row: usize, col: usize) -> Vec<char> {
+        ALL_AROUND.iter()
+            .filter_map(|direction| self.seats.ray(row, col, *direction).find(|(_, _, seat)| **seat != '.'))
+            .map(|(_, _, seat)| *seat)
+            .collect()
     }
 
-    fn can_be_occupied(neighbours: &Vec<(usize, usize, char)>) -> bool {
+    fn can_be_occupied(neighbours: &[char]) -> bool {
         neighbours.iter()
-            .filter(|(_, _, seat)| *seat == '#')
+            .filter(|seat| **seat == '#')
             .count() == 0
     }
 
-    fn can_be_emptied(neighbours: &Vec<(usize, usize, char)>, min_num: usize) -> bool {
+    fn can_be_emptied(neighbours: &[char], min_num: usize) -> bool {
         neighbours.iter()
-            .filter(|(_, _, seat)| *seat == '#')
+            .filter(|seat| **seat == '#')
             .count() >= min_num
     }
 
-    pub fn shuffle_seats(&mut self) {
-        let mut seats = self.get_seats_copy();
+    fn shuffle<F>(&mut self, get_neighbours: F, min_to_empty: usize)
+    where
+        F: Fn(&Self, usize, usize) -> Vec<char>
+    {
+        let mut seats = self.seats.clone();
 
         let mut changed_state = false;
-        for (row_pos, each_row) in self.seats.iter().enumerate() {
-            for (pos_in_row, each_seat) in each_row.iter().enumerate() {
-                if *each_seat != '.' {
-                    let neighbours = self.get_neighbours(&row_pos, &pos_in_row);
-                    if *each_seat != '#' && Self::can_be_occupied(&neighbours) {
-                        seats[row_pos][pos_in_row] = '#';
-                        changed_state = true;
-                    }
-                    if *each_seat != 'L' && Self::can_be_emptied(&neighbours, 4) {
-                        seats[row_pos][pos_in_row] = 'L';
-                        changed_state = true;
-                    }
+        for (row, col, each_seat) in self.seats.iter() {
+            if *each_seat != '.' {
+                let neighbours = get_neighbours(self, row, col);
+                if *each_seat != '#' && Self::can_be_occupied(&neighbours) {
+                    seats[(row, col)] = '#';
+                    changed_state = true;
+                }
+                if *each_seat != 'L' && Self::can_be_emptied(&neighbours, min_to_empty) {
+                    seats[(row, col)] = 'L';
+                    changed_state = true;
                 }
             }
         }
@@ -117,131 +76,57 @@ impl Ferry {
         self.seats = seats;
     }
 
-    pub fn shuffle_far_seats(&mut self) {
-        let mut seats = self.get_seats_copy();
-
-        let mut changed_state = false;
-        for (row_pos, each_row) in self.seats.iter().enumerate() {
-            for (pos_in_row, each_seat) in each_row.iter().enumerate() {
-                if *each_seat != '.' {
-                    let neighbours = self.get_far_neighbours(&row_pos, &pos_in_row);
-                    if *each_seat != '#' && Self::can_be_occupied(&neighbours) {
-                        seats[row_pos][pos_in_row] = '#';
-                        changed_state = true;
-                    }
-                    if *each_seat != 'L' && Self::can_be_emptied(&neighbours, 5) {
-                        seats[row_pos][pos_in_row] = 'L';
-                        changed_state = true;
-                    }
-                }
-            }
-        }
-
-        if !changed_state {
-            self.seats_state_stable = true
-        }
+    pub fn shuffle_seats(&mut self) {
+        self.shuffle(Self::get_neighbours, 4);
+    }
 
-        self.seats = seats;
+    pub fn shuffle_far_seats(&mut self) {
+        self.shuffle(Self::get_far_neighbours, 5);
     }
 
     pub fn get_occupied(&self) -> u64 {
-        let mut ans = 0;
-        for r in &self.seats {
-            for s in r {
-                if *s == '#' {
-                    ans += 1;
-                }
-            }
-        }
-        ans
+        self.seats.iter()
+            .filter(|(_, _, seat)| **seat == '#')
+            .count() as u64
     }
 }
 
+fn parse_seats(input: &[String]) -> Result<Grid<char>, PuzzleError> {
+    Grid::parse(input, |c| match c {
+        '.' | 'L' | '#' => Ok(c),
+        _ => Err(format!("unexpected '{}', expected '.', 'L' or '#'", c))
+    })
+}
+
 pub struct Puzzle {}
 
 impl Solution for Puzzle {
     type PuzzleInput = String;
+    type Parsed = Grid<char>;
     type OutputPartOne = u64;
     type OutputPartTwo = u64;
 
-    fn solve_part_one(input: &Vec<Self::PuzzleInput>) -> Self::OutputPartOne {
-        let mut seats: Vec<Vec<char>> = vec![vec![]; input.len()];
-        for (i, each_row) in input.iter().enumerate() {
-            seats[i] = each_row.chars().map(|c| c).collect();
-        }
+    fn parse(_input: Vec<Self::PuzzleInput>) -> Result<Self::Parsed, PuzzleError> {
+        parse_seats(&_input)
+    }
 
-        let mut ferry = Ferry::new(seats);
+    fn solve_part_one(input: &Self::Parsed) -> Result<Self::OutputPartOne, PuzzleError> {
+        let mut ferry = Ferry::new(input.clone());
 
         while !ferry.seats_state_stable {
             ferry.shuffle_seats();
         }
 
-        ferry.get_occupied()
+        Ok(ferry.get_occupied())
     }
 
-    fn solve_part_two(input: &Vec<Self::PuzzleInput>) -> Self::OutputPartTwo {
-        let mut seats: Vec<Vec<char>> = vec![vec![]; input.len()];
-        for (i, each_row) in input.iter().enumerate() {
-            seats[i] = each_row.chars().map(|c| c).collect();
-        }
-
-        let mut ferry = Ferry::new(seats);
+    fn solve_part_two(input: &Self::Parsed) -> Result<Self::OutputPartTwo, PuzzleError> {
+        let mut ferry = Ferry::new(input.clone());
 
         while !ferry.seats_state_stable {
             ferry.shuffle_far_seats();
         }
 
-        ferry.get_occupied()
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::puzzles::day_11::*;
-
-    #[test]
-    fn test_part_one() {
-        // given
-        let _input = vec![
-            "L.LL.LL.LL",
-            "LLLLLLL.LL",
-            "L.L.L..L..",
-            "LLLL.LL.LL",
-            "L.LL.LL.LL",
-            "L.LLLLL.LL",
-            "..L.L.....",
-            "LLLLLLLLLL",
-            "L.LLLLLL.L",
-            "L.LLLLL.LL",
-        ].into_iter().map(|_i| String::from(_i)).collect();
-
-        // when
-        let _res: u64 = Puzzle::solve_part_one(&_input);
-
-        // then
-        assert_eq!(_res, 37);
-    }
-
-    #[test]
-    fn test_part_two() {
-        // given
-        let _input = vec![
-            "L.LL.LL.LL",
-            "LLLLLLL.LL",
-            "L.L.L..L..",
-            "LLLL.LL.LL",
-            "L.LL.LL.LL",
-            "L.LLLLL.LL",
-            "..L.L.....",
-            "LLLLLLLLLL",
-            "L.LLLLLL.L",
-            "L.LLLLL.LL",
-        ].into_iter().map(|_i| String::from(_i)).collect();
-
-        // when
-        let _res: u64 = Puzzle::solve_part_two(&_input);
-
-        // then
-        assert_eq!(_res, 26);
+        Ok(ferry.get_occupied())
     }
 }
\ No newline at end of file
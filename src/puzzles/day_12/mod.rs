@@ -1,8 +1,19 @@
-use crate::puzzles::solution::Solution;
+use crate::error::PuzzleError;
+use crate::puzzles::lint::Diagnostic;
+use crate::puzzles::metadata::{Metadata, Tag};
+use crate::puzzles::solution::{Solution, parse_lines};
 use std::str::FromStr;
 
-#[derive(Debug, PartialEq)]
-enum Action {
+pub const METADATA: Metadata = Metadata {
+    title: "Rain Risk",
+    tags: &[Tag::Geometry, Tag::Simulation],
+    summary: "Steer the ferry through navigation instructions and report its Manhattan distance, first moving the ship itself and then a waypoint around it."
+};
+
+const ACTION_FORM: &str = "an action N, S, E, W, L, R or F followed by a number, e.g. F10";
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Action {
     N(i32),
     S(i32),
     E(i32),
@@ -26,23 +37,30 @@ enum Turn {
     L
 }
 
+fn is_turn(angle: i32) -> bool {
+    angle > 0 && angle % 90 == 0
+}
+
 impl FromStr for Action {
-    type Err = ();
+    type Err = PuzzleError;
 
     fn from_str(input: &str) -> Result<Action, Self::Err> {
-        fn parse(val: &str) -> i32 {
-            val.parse::<i32>().unwrap()
-        }
+        let parse = |val: &str| val.parse::<i32>().map_err(|e| PuzzleError::parse(input, e));
+        // the ferry only turns clockwise or counterclockwise by whole quarters
+        let turn = |val: &str| match parse(val)? {
+            angle if is_turn(angle) => Ok(angle),
+            _ => Err(PuzzleError::parse(input, "expected a turn by a positive multiple of 90 degrees"))
+        };
 
-        match &input[0..1] {
-            "N"  => Ok(Action::N(parse(&input[1..]))),
-            "S"  => Ok(Action::S(parse(&input[1..]))),
-            "E"  => Ok(Action::E(parse(&input[1..]))),
-            "W" => Ok(Action::W(parse(&input[1..]))),
-            "L" => Ok(Action::L(parse(&input[1..]))),
-            "R" => Ok(Action::R(parse(&input[1..]))),
-            "F" => Ok(Action::F(parse(&input[1..]))),
-            _      => Err(()),
+        match input.get(0..1) {
+            Some("N")  => Ok(Action::N(parse(&input[1..])?)),
+            Some("S")  => Ok(Action::S(parse(&input[1..])?)),
+            Some("E")  => Ok(Action::E(parse(&input[1..])?)),
+            Some("W") => Ok(Action::W(parse(&input[1..])?)),
+            Some("L") => Ok(Action::L(turn(&input[1..])?)),
+            Some("R") => Ok(Action::R(turn(&input[1..])?)),
+            Some("F") => Ok(Action::F(parse(&input[1..])?)),
+            _      => Err(PuzzleError::parse(input, "expected action N, S, E, W, L, R or F")),
         }
     }
 }
@@ -59,6 +77,12 @@ pub struct Ferry {
     waypoint: Waypoint
 }
 
+impl Default for Ferry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Ferry {
     pub fn new() -> Self {
         Self {
@@ -77,7 +101,7 @@ impl Ferry {
             Dir::N | Dir::S => &mut self.y_pos,
             Dir::W | Dir::E => &mut self.x_pos
         };
-        let change_val = if dir == Dir::S || dir == Dir::W {-1 * change_val} else {change_val};
+        let change_val = if dir == Dir::S || dir == Dir::W {-change_val} else {change_val};
         *pos += change_val;
     }
 
@@ -86,25 +110,20 @@ impl Ferry {
             Dir::N | Dir::S => &mut self.waypoint.y_pos,
             Dir::W | Dir::E => &mut self.waypoint.x_pos
         };
-        let change_val = if dir == Dir::S || dir == Dir::W {-1 * change_val} else {change_val};
+        let change_val = if dir == Dir::S || dir == Dir::W {-change_val} else {change_val};
         *pos += change_val;
     }
 
     fn change_angle(&mut self, turn: Turn, angle: i32) {
-        let directions = vec![Dir::N, Dir::E, Dir::S, Dir::W];
-        let shift = (angle / 90) % 4;
+        let directions = [Dir::N, Dir::E, Dir::S, Dir::W];
+        let shift = angle / 90;
 
         let curr = directions.iter().position(|p| *p == self.last_dir).unwrap() as i32;
 
-        let new_pos = if turn == Turn::R {(curr + shift)% 4} else {
-            let mut pos = (curr - shift) % 4;
-            if pos < 0 {
-                pos = directions.len() as i32 + pos;
-            }
-            pos
-        };
+        // always within 0..4, whatever the angle
+        let new_pos = (if turn == Turn::R { curr + shift } else { curr - shift }).rem_euclid(4);
 
-        self.last_dir = *directions.get(new_pos as usize).unwrap();
+        self.last_dir = directions[new_pos as usize];
     }
 
     fn rotate_by_90(&mut self, turn: &Turn) {
@@ -123,7 +142,7 @@ impl Ferry {
     }
 
     fn rotate_waypoint(&mut self, turn: Turn, angle: i32) {
-        let shift = (angle / 90) % 4;
+        let shift = (angle / 90).rem_euclid(4);
 
         for _ in 0..shift {
             self.rotate_by_90(&turn)
@@ -196,24 +215,63 @@ pub struct Puzzle {}
 
 impl Solution for Puzzle {
     type PuzzleInput = String;
+    type Parsed = Vec<Action>;
     type OutputPartOne = i32;
     type OutputPartTwo = i32;
 
-    fn solve_part_one(input: &Vec<Self::PuzzleInput>) -> Self::OutputPartOne {
+    fn parse(_input: Vec<Self::PuzzleInput>) -> Result<Self::Parsed, PuzzleError> {
+        parse_lines(&_input, Action::from_str)
+    }
+
+    fn validate(input: &[String]) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+
+        for (i, line) in input.iter().enumerate() {
+            let action = match line.chars().next() {
+                Some(action) => action,
+                None => {
+                    diagnostics.push(Diagnostic::end_of(i + 1, line, "empty line").expected(ACTION_FORM));
+                    continue;
+                }
+            };
+            let (name, value) = line.split_at(action.len_utf8());
+
+            if !"NSEWLRF".contains(action) {
+                diagnostics.push(Diagnostic::token(i + 1, line, name, format!("unknown action '{}'", action)).expected(ACTION_FORM));
+                continue;
+            }
+
+            match value.parse::<i32>() {
+                Err(_) if value.is_empty() => {
+                    diagnostics.push(Diagnostic::end_of(i + 1, line, "missing value").expected("a number after the action, e.g. F10"));
+                },
+                Err(e) => {
+                    diagnostics.push(Diagnostic::token(i + 1, line, value, format!("invalid value '{}', {}", value, e)).expected("a whole number"));
+                },
+                Ok(angle) if matches!(action, 'L' | 'R') && !is_turn(angle) => {
+                    diagnostics.push(Diagnostic::token(i + 1, line, value, "turns have to be positive multiples of 90 degrees").expected("90, 180 or 270"));
+                },
+                Ok(_) => {}
+            }
+        }
+
+        diagnostics
+    }
+
+    fn solve_part_one(input: &Self::Parsed) -> Result<Self::OutputPartOne, PuzzleError> {
         let mut ferry = Ferry::new();
         for c in input {
-            ferry.move_next(Action::from_str(c).unwrap());
+            ferry.move_next(*c);
         }
-        ferry.get_distance()
+        Ok(ferry.get_distance())
     }
 
-    fn solve_part_two(input: &Vec<Self::PuzzleInput>) -> Self::OutputPartTwo {
+    fn solve_part_two(input: &Self::Parsed) -> Result<Self::OutputPartTwo, PuzzleError> {
         let mut ferry = Ferry::new();
         for c in input {
-            ferry.move_next_with_waypoint(Action::from_str(c).unwrap());
-            // ferry.get_pos();
+            ferry.move_next_with_waypoint(*c);
         }
-        ferry.get_distance()
+        Ok(ferry.get_distance())
     }
 }
 
@@ -222,38 +280,52 @@ mod tests {
     use crate::puzzles::day_12::*;
 
     #[test]
-    fn test_part_one() {
+    fn test_validate() {
+        // given
+        let _input = vec!["F10", "X3", "R45", "N", "E1x", "L-180", "F7"].into_iter().map(String::from).collect::<Vec<String>>();
+
+        // when
+        let _res: Vec<(Option<usize>, usize, usize, String)> = Puzzle::validate(&_input)
+            .into_iter()
+            .map(|_d| (_d.line, _d.column, _d.len, _d.message))
+            .collect();
+
+        // then
+        assert_eq!(_res, vec![
+            (Some(2), 1, 1, "unknown action 'X'".to_string()),
+            (Some(3), 2, 2, "turns have to be positive multiples of 90 degrees".to_string()),
+            (Some(4), 2, 1, "missing value".to_string()),
+            (Some(5), 2, 2, "invalid value '1x', invalid digit found in string".to_string()),
+            (Some(6), 2, 4, "turns have to be positive multiples of 90 degrees".to_string())
+        ]);
+    }
+
+    #[test]
+    fn test_parse_rejects_what_validate_rejects() {
         // given
-        let _input = vec![
-            "F10",
-            "N3",
-            "F7",
-            "R90",
-            "F11"
-        ].into_iter().map(|_i| String::from(_i)).collect();
+        let _turns = ["R90", "L270", "R450", "R45", "R-180", "L0"];
 
         // when
-        let _res: i32 = Puzzle::solve_part_one(&_input);
+        let _res_parse: Vec<bool> = _turns.iter().map(|_t| _t.parse::<Action>().is_ok()).collect();
+        let _res_validate: Vec<bool> = _turns.iter().map(|_t| Puzzle::validate(&[_t.to_string()]).is_empty()).collect();
 
         // then
-        assert_eq!(_res, 25);
+        assert_eq!(_res_parse, vec![true, true, true, false, false, false]);
+        assert_eq!(_res_parse, _res_validate);
     }
 
     #[test]
-    fn test_part_two() {
+    fn test_change_angle_wraps() {
         // given
-        let _input = vec![
-            "F10",
-            "N3",
-            "F7",
-            "R90",
-            "F11"
-        ].into_iter().map(|_i| String::from(_i)).collect();
+        let mut _ferry = Ferry::new();
 
         // when
-        let _res: i32 = Puzzle::solve_part_two(&_input);
+        _ferry.change_angle(Turn::L, 450);
+        let _res_left = _ferry.last_dir;
+        _ferry.change_angle(Turn::R, 990);
 
         // then
-        assert_eq!(_res, 286);
+        assert_eq!(_res_left, Dir::N);
+        assert_eq!(_ferry.last_dir, Dir::W);
     }
 }
\ No newline at end of file
@@ -1,21 +1,36 @@
+use crate::error::PuzzleError;
+use crate::puzzles::cancel;
+use crate::puzzles::lint::Diagnostic;
+use crate::puzzles::metadata::{Metadata, Tag};
 use crate::puzzles::solution::Solution;
 
+pub const METADATA: Metadata = Metadata {
+    title: "Shuttle Search",
+    tags: &[Tag::NumberTheory],
+    summary: "Find the earliest bus to the airport, then the earliest timestamp at which the buses depart at offsets matching their position in the list."
+};
+
 #[derive(Debug, Copy, Clone)]
-struct Bus {
+pub struct Bus {
     pub id: u64,
     relative_timestamp_offset: usize
 }
 
 impl Bus {
-    pub fn new(id: String, timestamp_offset: usize) -> Self {
-        Self {
-            id: id.parse::<u64>().unwrap(),
-            relative_timestamp_offset: timestamp_offset
+    pub fn new(id: String, timestamp_offset: usize) -> Result<Self, PuzzleError> {
+        let parsed_id = id.parse::<u64>().map_err(|e| PuzzleError::parse(&id, e))?;
+        if parsed_id == 0 {
+            return Err(PuzzleError::parse(&id, "bus id must be positive"));
         }
+
+        Ok(Self {
+            id: parsed_id,
+            relative_timestamp_offset: timestamp_offset
+        })
     }
 
     fn get_earliest(&self, timestamp: u64) -> u64 {
-        if timestamp % self.id == 0 {
+        if timestamp.is_multiple_of(self.id) {
             return timestamp
         };
 
@@ -25,7 +40,7 @@ impl Bus {
     }
 
     fn get_timestamp_for_iteration(&self, iteration: u64) -> u64 {
-        iteration * self.id as u64
+        iteration * self.id
     }
 
     fn is_ok_relative_to_first(&self, prev_timestamp: u64, timestamp: u64) -> bool {
@@ -33,27 +48,104 @@ impl Bus {
     }
 }
 
+fn get_schedule(input: &[String]) -> Result<&str, PuzzleError> {
+    input
+        .get(1)
+        .map(|s| s.as_str())
+        .ok_or_else(|| PuzzleError::parse("", "missing bus schedule").at_line(2))
+}
+
+pub struct Notes {
+    earliest_timestamp: u64,
+    buses: Vec<Bus>
+}
+
 pub struct Puzzle {}
 
 impl Solution for Puzzle {
     type PuzzleInput = String;
+    type Parsed = Notes;
     type OutputPartOne = u64;
     type OutputPartTwo = u64;
 
-    fn solve_part_one(input: &Vec<Self::PuzzleInput>) -> Self::OutputPartOne {
-        let operating_buses: Vec<Bus> = input
-            .get(1).unwrap()
+    fn parse(input: Vec<Self::PuzzleInput>) -> Result<Self::Parsed, PuzzleError> {
+        let earliest_timestamp = input.first().map_or("", |s| s.as_str());
+        let earliest_timestamp = earliest_timestamp.parse::<u64>()
+            .map_err(|e| PuzzleError::parse(earliest_timestamp, e).at_line(1))?;
+
+        let buses: Vec<Bus> = get_schedule(&input)?
             .split(',')
-            .filter(|i| *i != "x")
-            .map(|i| Bus::new(i.to_string(), 0))
-            .collect();
+            .enumerate()
+            .filter_map(|(i, b)| {
+                if b != "x" {
+                    return Some(Bus::new(b.to_string(), i))
+                }
+                None
+            })
+            .collect::<Result<Vec<Bus>, PuzzleError>>()
+            .map_err(|e| e.at_line(2))?;
+
+        Ok(Notes {
+            earliest_timestamp,
+            buses
+        })
+    }
+
+    fn validate(input: &[String]) -> Vec<Diagnostic> {
+        let mut diagnostics = vec![];
+
+        let timestamp = match input.first() {
+            Some(timestamp) => timestamp,
+            None => return vec![Diagnostic::file("empty input").expected("a timestamp and a bus schedule")]
+        };
+        if timestamp.parse::<u64>().is_err() {
+            diagnostics.push(
+                Diagnostic::line(1, timestamp, format!("invalid timestamp '{}'", timestamp))
+                    .expected("the earliest departure as a whole number, e.g. 939")
+            );
+        }
 
-        let earliest_timestamp = input.get(0).unwrap().parse::<u64>().unwrap();
+        let schedule = match input.get(1) {
+            Some(schedule) => schedule,
+            None => {
+                diagnostics.push(Diagnostic::end_of(1, timestamp, "missing bus schedule").expected("bus ids on the next line, e.g. 7,13,x,59"));
+                return diagnostics;
+            }
+        };
+
+        let mut buses = 0;
+        for id in schedule.split(',') {
+            let problem = match id.parse::<u64>() {
+                _ if id == "x" => continue,
+                Ok(0) => "bus ids have to be positive".to_string(),
+                Ok(_) => {
+                    buses += 1;
+                    continue;
+                },
+                Err(_) if id.is_empty() => "missing bus id".to_string(),
+                Err(_) => format!("invalid bus id '{}'", id)
+            };
+            diagnostics.push(Diagnostic::token(2, schedule, id, problem).expected("a bus id or x"));
+        }
+        if buses == 0 && diagnostics.is_empty() {
+            diagnostics.push(Diagnostic::line(2, schedule, "no operating buses").expected("at least one bus id"));
+        }
+
+        for (i, line) in input.iter().enumerate().skip(2).filter(|(_, l)| !l.is_empty()) {
+            diagnostics.push(Diagnostic::line(i + 1, line, "unexpected line").expected("only a timestamp and a bus schedule"));
+        }
+
+        diagnostics
+    }
+
+    fn solve_part_one(input: &Self::Parsed) -> Result<Self::OutputPartOne, PuzzleError> {
+        let operating_buses = &input.buses;
+        let earliest_timestamp = input.earliest_timestamp;
 
         let mut min = u64::MAX;
         let mut ans = 0;
 
-        for b in &operating_buses {
+        for b in operating_buses {
             let closest_timestamp = b.get_earliest(earliest_timestamp);
             if closest_timestamp < min {
                 min = closest_timestamp;
@@ -61,35 +153,30 @@ impl Solution for Puzzle {
             }
         }
 
-        ans
+        Ok(ans)
     }
 
-    fn solve_part_two(input: &Vec<Self::PuzzleInput>) -> Self::OutputPartTwo {
-        let operating_buses: Vec<Bus> = input
-            .get(1).unwrap()
-            .split(',')
-            .enumerate()
-            .map(|(i, b)| {
-                if b != "x" {
-                    return Some(Bus::new(b.to_string(), i))
-                }
-                None
-            })
-            .filter(|b| b.is_some())
-            .map(|b| b.unwrap())
-            .collect();
+    fn solve_part_two(input: &Self::Parsed) -> Result<Self::OutputPartTwo, PuzzleError> {
+        let operating_buses = &input.buses;
+
+        if operating_buses.is_empty() {
+            return Err(PuzzleError::solve("no operating buses"));
+        }
         
         let mut iter: u64 = 1;
         loop {
             iter += 1;
+            if iter.is_multiple_of(1 << 16) {
+                cancel::check()?;
+            }
 
             let mut busses_checked = 0;
             let mut first_bus_timestamp: u64 = 0;
             let mut should_skip = false;
 
-            for b in &operating_buses {
+            for b in operating_buses {
                 busses_checked += 1;
-                if should_skip == true {
+                if should_skip {
                     should_skip = false;
                     break;
                 }
@@ -107,7 +194,7 @@ impl Solution for Puzzle {
             }
 
             if !should_skip {
-                return first_bus_timestamp;
+                return Ok(first_bus_timestamp);
             }
         }
     }
@@ -118,67 +205,40 @@ mod tests {
     use crate::puzzles::day_13::*;
 
     #[test]
-    fn test_part_one() {
+    fn test_parse_timestamp() {
         // given
-        let _input = vec![
-            "939",
-            "7,13,x,x,59,x,31,19"
-        ].into_iter().map(|_i| String::from(_i)).collect();
+        let _input = vec!["939".to_string(), "7,13,x,x,59".to_string()];
+        let _input_invalid = vec!["93 9".to_string(), "7,13,x,x,59".to_string()];
 
         // when
-        let _res = Puzzle::solve_part_one(&_input);
+        let _res = Puzzle::parse(_input).unwrap();
+        let _res_invalid = Puzzle::parse(_input_invalid).err().unwrap();
 
         // then
-        assert_eq!(_res, 295);
+        assert_eq!(_res.earliest_timestamp, 939);
+        assert_eq!(_res_invalid.to_string(), "line 1: invalid input, invalid digit found in string in '93 9'");
     }
 
     #[test]
-    fn test_part_two() {
+    fn test_validate() {
         // given
-        let input1 = vec![
-            "_",
-            "7,13,x,x,59,x,31,19"
-        ].into_iter().map(|_i| String::from(_i)).collect();
-
-        let input2 = vec![
-            "_",
-            "17,x,13,19"
-        ].into_iter().map(|_i| String::from(_i)).collect();
-
-        let input3 = vec![
-            "_",
-            "67,7,59,61"
-        ].into_iter().map(|_i| String::from(_i)).collect();
-
-        let input4 = vec![
-            "_",
-            "67,x,7,59,61"
-        ].into_iter().map(|_i| String::from(_i)).collect();
-
-        let input5 = vec![
-            "_",
-            "67,7,x,59,61"
-        ].into_iter().map(|_i| String::from(_i)).collect();
-
-        let input6 = vec![
-            "_",
-            "1789,37,47,1889"
-        ].into_iter().map(|_i| String::from(_i)).collect();
+        let _input = vec!["939", "7,13,,0,y7,19", "", "8"].into_iter().map(String::from).collect::<Vec<String>>();
+        let _input_missing = vec!["93 9".to_string()];
 
         // when
-        let res1 = Puzzle::solve_part_two(&input1);
-        let res2 = Puzzle::solve_part_two(&input2);
-        let res3 = Puzzle::solve_part_two(&input3);
-        let res4 = Puzzle::solve_part_two(&input4);
-        let res5 = Puzzle::solve_part_two(&input5);
-        let res6 = Puzzle::solve_part_two(&input6);
+        let _res: Vec<(Option<usize>, usize, usize, String)> = Puzzle::validate(&_input)
+            .into_iter()
+            .map(|_d| (_d.line, _d.column, _d.len, _d.message))
+            .collect();
+        let _res_missing: Vec<String> = Puzzle::validate(&_input_missing).into_iter().map(|_d| _d.message).collect();
 
         // then
-        assert_eq!(res1, 1068781);
-        assert_eq!(res2, 3417);
-        assert_eq!(res3, 754018);
-        assert_eq!(res4, 779210);
-        assert_eq!(res5, 1261476);
-        assert_eq!(res6, 1202161486);
+        assert_eq!(_res, vec![
+            (Some(2), 6, 1, "missing bus id".to_string()),
+            (Some(2), 7, 1, "bus ids have to be positive".to_string()),
+            (Some(2), 9, 2, "invalid bus id 'y7'".to_string()),
+            (Some(4), 1, 1, "unexpected line".to_string())
+        ]);
+        assert_eq!(_res_missing, vec!["invalid timestamp '93 9'", "missing bus schedule"]);
     }
 }
\ No newline at end of file
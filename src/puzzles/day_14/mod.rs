@@ -1,35 +1,48 @@
 use std::collections::HashMap;
 
-use crate::puzzles::solution::Solution;
+use crate::error::PuzzleError;
+use crate::puzzles::metadata::{Metadata, Tag};
+use crate::puzzles::solution::{Solution, parse_lines};
+
+pub const METADATA: Metadata = Metadata {
+    title: "Docked Data",
+    tags: &[Tag::Bits, Tag::Simulation],
+    summary: "Run the docking program's initialization with bitmasks applied to the values written, then with floating bits in the masks applied to the addresses."
+};
 
 #[derive(Debug)]
-enum Operation {
+pub enum Operation {
     Mask(String),
     Mem(usize, usize),
 }
 
-fn parse(input: &Vec<String>) -> Vec<Operation> {
-    let mut memory_operations: Vec<Operation> = vec![];
-
-    for i in input {
-       match &i[0..3] {
-            "mas" => {
-                let mask: &str = i.split('=').collect::<Vec<&str>>().get(1).unwrap().trim();
-                let mask = Operation::Mask(mask.to_string());
-                memory_operations.push(mask);
-            },
-            "mem" => {
-                let op: Vec<&str> = i.split('=').collect();
-                let val = op.get(1).unwrap().trim();
-                let addr = op.get(0).unwrap().trim();
-                let addr = &addr[4..addr.len()-1];
-                memory_operations.push(Operation::Mem(addr.parse::<usize>().unwrap(), val.parse::<usize>().unwrap()));
-            },
-            _ => unreachable!()
-       }
+fn parse_operation(i: &str) -> Result<Operation, PuzzleError> {
+    let op: Vec<&str> = i.split('=').map(|o| o.trim()).collect();
+    let (target, val) = match op[..] {
+        [target, val] => (target, val),
+        _ => return Err(PuzzleError::parse(i, "expected '<target> = <value>'"))
+    };
+
+    if target == "mask" {
+        if val.len() != 36 || val.chars().any(|c| !"01X".contains(c)) {
+            return Err(PuzzleError::parse(i, "expected mask of 36 '0', '1' or 'X' characters"));
+        }
+        return Ok(Operation::Mask(val.to_string()));
     }
-    
-    memory_operations
+
+    let addr = target
+        .strip_prefix("mem[")
+        .and_then(|a| a.strip_suffix(']'))
+        .ok_or_else(|| PuzzleError::parse(i, "expected 'mask' or 'mem[<address>]'"))?;
+
+    Ok(Operation::Mem(
+        addr.parse::<usize>().map_err(|e| PuzzleError::parse(i, e))?,
+        val.parse::<usize>().map_err(|e| PuzzleError::parse(i, e))?
+    ))
+}
+
+fn parse_operations(input: &[String]) -> Result<Vec<Operation>, PuzzleError> {
+    parse_lines(input, parse_operation)
 }
 
 fn apply_mask_p1(value: &usize, mask: &str) -> usize {
@@ -44,52 +57,33 @@ pub struct Puzzle {}
 
 impl Solution for Puzzle {
     type PuzzleInput = String;
+    type Parsed = Vec<Operation>;
     type OutputPartOne = usize;
     type OutputPartTwo = usize;
 
-    fn solve_part_one(input: &Vec<Self::PuzzleInput>) -> Self::OutputPartOne {
-        let memory_operations = parse(input);
+    fn parse(_input: Vec<Self::PuzzleInput>) -> Result<Self::Parsed, PuzzleError> {
+        parse_operations(&_input)
+    }
 
+    fn solve_part_one(input: &Self::Parsed) -> Result<Self::OutputPartOne, PuzzleError> {
         let mut memory: HashMap<usize, usize> = HashMap::new();
-        let mut mask = "".to_string();
+        let mut mask = "X".repeat(36);
 
-        for op in memory_operations {
+        for op in input {
             match op {
                 Operation::Mem(addr, val) => {
-                    memory.insert(addr, apply_mask_p1(&val, &mask));
+                    memory.insert(*addr, apply_mask_p1(val, &mask));
                 }
                 Operation::Mask(m) => {
-                    mask = m
+                    mask = m.clone()
                 }
             }
         }
 
-        memory.values().sum()
+        Ok(memory.values().sum())
     }
 
-    fn solve_part_two(input: &Vec<Self::PuzzleInput>) -> Self::OutputPartTwo {
-        0
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use crate::puzzles::day_14::*;
-
-    #[test]
-    fn test_part_one() {
-        // given
-        let _input = vec![
-            "mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X",
-            "mem[8] = 11",
-            "mem[7] = 101",
-            "mem[8] = 0"
-        ].into_iter().map(|_i| String::from(_i)).collect();
-
-        // when
-        let _res: usize = Puzzle::solve_part_one(&_input);
-
-        // then
-        assert_eq!(_res, 165);
+    fn solve_part_two(_input: &Self::Parsed) -> Result<Self::OutputPartTwo, PuzzleError> {
+        Ok(0)
     }
 }
\ No newline at end of file
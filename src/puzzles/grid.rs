@@ -0,0 +1,210 @@
+use std::{
+    fmt::{self, Display},
+    iter::successors,
+    ops::{Index, IndexMut}
+};
+
+use crate::error::PuzzleError;
+use crate::puzzles::solution::parse_lines;
+
+// (row, col) steps to the adjacent cells
+pub const ORTHOGONAL: &[(isize, isize)] = &[(-1, 0), (0, -1), (0, 1), (1, 0)];
+pub const ALL_AROUND: &[(isize, isize)] = &[(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)];
+
+// rectangular grid stored row by row, positions are (row, col) from the top-left
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>
+}
+
+impl<T> Grid<T> {
+    pub fn from_fn<F>(width: usize, height: usize, mut cell: F) -> Self
+    where
+        F: FnMut(usize, usize) -> T
+    {
+        let cells = (0..width * height).map(|i| cell(i / width, i % width)).collect();
+        Self { width, height, cells }
+    }
+
+    // every line is a row, `parse_cell` rejects a character with a reason
+    pub fn parse<F>(input: &[String], mut parse_cell: F) -> Result<Self, PuzzleError>
+    where
+        F: FnMut(char) -> Result<T, String>
+    {
+        let width = input.first().map_or(0, |row| row.chars().count());
+        if width == 0 {
+            return Err(PuzzleError::parse("", "grid is empty"));
+        }
+
+        let rows = parse_lines(input, |row| {
+            if row.chars().count() != width {
+                return Err(PuzzleError::parse(row, format!("expected row of width {}", width)));
+            }
+            row.chars()
+                .map(|c| parse_cell(c).map_err(|reason| PuzzleError::parse(row, reason)))
+                .collect::<Result<Vec<T>, PuzzleError>>()
+        })?;
+
+        Ok(Self {
+            width,
+            height: rows.len(),
+            cells: rows.into_iter().flatten().collect()
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, row: usize, col: usize) -> Option<&T> {
+        if row < self.height && col < self.width {
+            return self.cells.get(row * self.width + col);
+        }
+        None
+    }
+
+    pub fn get_mut(&mut self, row: usize, col: usize) -> Option<&mut T> {
+        if row < self.height && col < self.width {
+            return self.cells.get_mut(row * self.width + col);
+        }
+        None
+    }
+
+    // the grid repeats itself in both directions
+    pub fn get_wrapping(&self, row: usize, col: usize) -> &T {
+        &self[(row % self.height, col % self.width)]
+    }
+
+    // position one step away, None when it falls off the grid
+    pub fn step(&self, row: usize, col: usize, (d_row, d_col): (isize, isize)) -> Option<(usize, usize)> {
+        let row = row.checked_add_signed(d_row)?;
+        let col = col.checked_add_signed(d_col)?;
+        if row < self.height && col < self.width {
+            return Some((row, col));
+        }
+        None
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize, &T)> + '_ {
+        self.cells.iter().enumerate().map(move |(i, cell)| (i / self.width, i % self.width, cell))
+    }
+
+    pub fn neighbours(&self, row: usize, col: usize, directions: &'static [(isize, isize)]) -> impl Iterator<Item = (usize, usize, &T)> + '_ {
+        directions.iter()
+            .filter_map(move |&d| self.step(row, col, d))
+            .map(move |(r, c)| (r, c, &self[(r, c)]))
+    }
+
+    // cells seen walking from (row, col) in one direction, the start excluded
+    pub fn ray(&self, row: usize, col: usize, direction: (isize, isize)) -> impl Iterator<Item = (usize, usize, &T)> + '_ {
+        successors(self.step(row, col, direction), move |&(r, c)| self.step(r, c, direction))
+            .map(move |(r, c)| (r, c, &self[(r, c)]))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, col): (usize, usize)) -> &T {
+        let (width, height) = (self.width, self.height);
+        self.get(row, col)
+            .unwrap_or_else(|| panic!("({}, {}) is outside of the {}x{} grid", row, col, width, height))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (row, col): (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(row, col)
+            .unwrap_or_else(|| panic!("({}, {}) is outside of the {}x{} grid", row, col, width, height))
+    }
+}
+
+// renders back to the text it was parsed from
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (row, col, cell) in self.iter() {
+            if col == 0 && row > 0 {
+                writeln!(f)?;
+            }
+            write!(f, "{}", cell)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::puzzles::grid::*;
+
+    fn parse_chars(_rows: &[&str]) -> Result<Grid<char>, PuzzleError> {
+        let _input = _rows.iter().map(|_r| _r.to_string()).collect::<Vec<String>>();
+        Grid::parse(&_input, |_c| match _c {
+            '.' | '#' => Ok(_c),
+            _ => Err(format!("unexpected '{}'", _c))
+        })
+    }
+
+    #[test]
+    fn test_parse_and_render() {
+        // given
+        let _rows = ["..#", "#..", ".#."];
+
+        // when
+        let _grid = parse_chars(&_rows).unwrap();
+
+        // then
+        assert_eq!((_grid.width(), _grid.height()), (3, 3));
+        assert_eq!(_grid[(1, 0)], '#');
+        assert_eq!(_grid.get(3, 0), None);
+        assert_eq!(_grid.to_string(), "..#\n#..\n.#.");
+    }
+
+    #[test]
+    fn test_parse_reports_line() {
+        // when
+        let _res_width = parse_chars(&["...", ".."]);
+        let _res_cell = parse_chars(&["...", "..x"]);
+        let _res_empty = parse_chars(&[]);
+
+        // then
+        assert_eq!(_res_width.unwrap_err().to_string(), "line 2: invalid input, expected row of width 3 in '..'");
+        assert_eq!(_res_cell.unwrap_err().to_string(), "line 2: invalid input, unexpected 'x' in '..x'");
+        assert!(_res_empty.is_err());
+    }
+
+    #[test]
+    fn test_neighbours() {
+        // given
+        let _grid = Grid::from_fn(3, 3, |_row, _col| _row * 3 + _col);
+
+        // when
+        let _corner: Vec<usize> = _grid.neighbours(0, 0, ALL_AROUND).map(|(_, _, _v)| *_v).collect();
+        let _middle: Vec<usize> = _grid.neighbours(1, 1, ORTHOGONAL).map(|(_, _, _v)| *_v).collect();
+
+        // then
+        assert_eq!(_corner, vec![1, 3, 4]);
+        assert_eq!(_middle, vec![1, 3, 5, 7]);
+    }
+
+    #[test]
+    fn test_ray_and_wrapping() {
+        // given
+        let _grid = Grid::from_fn(4, 2, |_row, _col| _row * 4 + _col);
+
+        // when
+        let _ray: Vec<usize> = _grid.ray(0, 0, (0, 1)).map(|(_, _, _v)| *_v).collect();
+        let _diagonal: Vec<(usize, usize)> = _grid.ray(1, 3, (-1, -1)).map(|(_r, _c, _)| (_r, _c)).collect();
+
+        // then
+        assert_eq!(_ray, vec![1, 2, 3]);
+        assert_eq!(_diagonal, vec![(0, 2)]);
+        assert_eq!(*_grid.get_wrapping(3, 9), 5);
+    }
+}
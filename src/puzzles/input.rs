@@ -0,0 +1,143 @@
+use std::{
+    env,
+    fs::{self, File},
+    io::{self, Read},
+    path::{Path, PathBuf}
+};
+
+use crate::error::PuzzleError;
+use crate::puzzles;
+
+pub const INPUT_DIR_VAR: &str = "AOC_INPUT_DIR";
+
+const INPUT_FILE_NAME: &str = "input.txt";
+const EXAMPLES_DIR_NAME: &str = "examples";
+const PUZZLES_DIR: &str = "src/puzzles";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    // $AOC_INPUT_DIR/<day>/input.txt, falling back to src/puzzles/<day>/input.txt
+    // and then to the copy embedded with the `embed-inputs` feature
+    Default,
+    File(PathBuf),
+    Stdin
+}
+
+impl InputSource {
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => InputSource::Stdin,
+            _ => InputSource::File(PathBuf::from(arg))
+        }
+    }
+
+    // path used for reading and error reporting, stdin gets a placeholder name
+    pub fn path(&self, day_name: &str) -> PathBuf {
+        match self {
+            InputSource::Default => default_path(env::var_os(INPUT_DIR_VAR).map(PathBuf::from), day_name),
+            InputSource::File(path) => path.clone(),
+            InputSource::Stdin => PathBuf::from("<stdin>")
+        }
+    }
+
+    // whole input is read up front so parsing can be timed without I/O
+    pub fn read(&self, day_name: &str) -> Result<(PathBuf, String), PuzzleError> {
+        let path = self.path(day_name);
+
+        if *self == InputSource::Default && !path.is_file() {
+            if let Some(text) = puzzles::embedded_input(day_name) {
+                return Ok((PathBuf::from(format!("<embedded {}>", day_name)), text.to_string()));
+            }
+        }
+
+        let mut text = String::new();
+        let read = match self {
+            InputSource::Stdin => io::stdin().read_to_string(&mut text),
+            _ => File::open(&path).and_then(|mut file| file.read_to_string(&mut text))
+        };
+        read.map_err(|e| PuzzleError::io(e).at_path(&path))?;
+
+        Ok((path, text))
+    }
+}
+
+// inputs of the checkout a development build was made from, a release build
+// must not look for them at a path of the machine it was built on
+#[cfg(debug_assertions)]
+fn source_dir() -> Option<PathBuf> {
+    Some(Path::new(env!("CARGO_MANIFEST_DIR")).join(PUZZLES_DIR))
+}
+
+#[cfg(not(debug_assertions))]
+fn source_dir() -> Option<PathBuf> {
+    None
+}
+
+fn default_path(input_dir: Option<PathBuf>, day_name: &str) -> PathBuf {
+    let candidates = input_dir
+        .into_iter()
+        .chain(env::current_dir().ok().map(|dir| dir.join(PUZZLES_DIR)))
+        .chain(source_dir())
+        .map(|dir| dir.join(day_name).join(INPUT_FILE_NAME))
+        .collect::<Vec<PathBuf>>();
+
+    candidates
+        .iter()
+        .find(|path| path.is_file())
+        .or_else(|| candidates.last())
+        .cloned()
+        .unwrap_or_else(|| Path::new(PUZZLES_DIR).join(day_name).join(INPUT_FILE_NAME))
+}
+
+// puzzle examples in src/puzzles/<day>/examples/<name>/input.txt, ordered by name
+pub fn examples(day_name: &str) -> Vec<(String, PathBuf)> {
+    let dir = default_path(None, day_name).with_file_name(EXAMPLES_DIR_NAME);
+
+    let mut examples: Vec<(String, PathBuf)> = fs::read_dir(dir)
+        .map(|entries| entries
+            .filter_map(Result::ok)
+            .map(|e| (e.file_name().to_string_lossy().into_owned(), e.path().join(INPUT_FILE_NAME)))
+            .filter(|(_, path)| path.is_file())
+            .collect())
+        .unwrap_or_default();
+    examples.sort();
+    examples
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::puzzles::input::*;
+
+    #[test]
+    fn test_from_arg() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(InputSource::from_arg("in.txt"), InputSource::File(PathBuf::from("in.txt")));
+    }
+
+    #[test]
+    fn test_default_path_prefers_input_dir() {
+        // given
+        let _dir = env::temp_dir().join("aoc_input_test_input_dir");
+        std::fs::create_dir_all(_dir.join("day_07")).unwrap();
+        std::fs::write(_dir.join("day_07").join(INPUT_FILE_NAME), "").unwrap();
+
+        // when
+        let _res_found = default_path(Some(_dir.clone()), "day_07");
+        let _res_fallback = default_path(Some(_dir.clone()), "day_01");
+
+        // then
+        assert_eq!(_res_found, _dir.join("day_07").join(INPUT_FILE_NAME));
+        assert_eq!(_res_fallback, Path::new(env!("CARGO_MANIFEST_DIR")).join("src/puzzles/day_01/input.txt"));
+        std::fs::remove_dir_all(&_dir).unwrap();
+    }
+
+    #[test]
+    fn test_examples() {
+        // when
+        let _res = examples("day_07");
+
+        // then
+        assert_eq!(_res.iter().map(|(name, _)| name.as_str()).collect::<Vec<&str>>(), vec!["example", "example_2"]);
+        assert!(_res.iter().all(|(_, path)| path.ends_with("input.txt")));
+    }
+}
@@ -0,0 +1,218 @@
+use std::{
+    fmt::Write,
+    path::PathBuf
+};
+
+use crate::error::PuzzleError;
+use crate::puzzles::{input::InputSource, panics::catch_panic, Day};
+
+// problem found in an input before solving it, lines and columns count from 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    // None when the input as a whole is the problem
+    pub line: Option<usize>,
+    pub column: usize,
+    // characters underlined, at least one
+    pub len: usize,
+    pub message: String,
+    // form the input should have, printed next to the caret
+    pub expected: Option<String>
+}
+
+impl Diagnostic {
+    // whole `text` of `line` underlined
+    pub fn line(line: usize, text: &str, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(line),
+            column: 1,
+            len: text.chars().count().max(1),
+            message: message.into(),
+            expected: None
+        }
+    }
+
+    // `token` underlined, it has to be a slice of `text`
+    pub fn token(line: usize, text: &str, token: &str, message: impl Into<String>) -> Self {
+        let offset = (token.as_ptr() as usize).saturating_sub(text.as_ptr() as usize).min(text.len());
+        debug_assert!(offset + token.len() <= text.len(), "token is not a slice of the line");
+
+        Self {
+            line: Some(line),
+            column: text[..offset].chars().count() + 1,
+            len: token.chars().count().max(1),
+            message: message.into(),
+            expected: None
+        }
+    }
+
+    // caret right after `text`, for something missing at the end of a line
+    pub fn end_of(line: usize, text: &str, message: impl Into<String>) -> Self {
+        Self {
+            line: Some(line),
+            column: text.chars().count() + 1,
+            len: 1,
+            message: message.into(),
+            expected: None
+        }
+    }
+
+    pub fn file(message: impl Into<String>) -> Self {
+        Self {
+            line: None,
+            column: 1,
+            len: 1,
+            message: message.into(),
+            expected: None
+        }
+    }
+
+    pub fn expected(mut self, form: impl Into<String>) -> Self {
+        self.expected = Some(form.into());
+        self
+    }
+
+    // days without a validator of their own only know the first error of their parser,
+    // the text it failed on is underlined when it is found on the line
+    pub fn from_error(lines: &[String], err: &PuzzleError) -> Self {
+        let line_text = err.line().and_then(|l| lines.get(l - 1));
+        match (err.line(), line_text) {
+            (Some(line), Some(text)) => match err.text().and_then(|t| text.find(t).filter(|_| !t.is_empty()).map(|i| &text[i..i + t.len()])) {
+                Some(token) => Self::token(line, text, token, err.reason()),
+                None => Self::line(line, text, err.reason())
+            },
+            (Some(line), None) => Self { line: Some(line), ..Self::file(err.reason()) },
+            (None, _) => Self::file(err.reason())
+        }
+    }
+}
+
+// input of one day with every problem found in it
+pub struct Lint {
+    pub puzzle_name: String,
+    pub path: PathBuf,
+    pub lines: Vec<String>,
+    pub diagnostics: Vec<Diagnostic>
+}
+
+pub fn lint(day: &Day, source: &InputSource) -> Lint {
+    let (path, lines, diagnostics) = match source.read(day.name) {
+        Ok((path, text)) => {
+            let lines: Vec<String> = text.lines().map(String::from).collect();
+            let diagnostics = catch_panic(|| (day.lint)(&lines))
+                .unwrap_or_else(|panic| vec![Diagnostic::file(format!("validator panicked: {}", panic))]);
+            (path, lines, diagnostics)
+        },
+        Err(err) => (source.path(day.name), vec![], vec![Diagnostic::file(err.reason())])
+    };
+
+    Lint { puzzle_name: day.name.to_string(), path, lines, diagnostics }
+}
+
+pub fn has_problems(lints: &[Lint]) -> bool {
+    lints.iter().any(|l| !l.diagnostics.is_empty())
+}
+
+// compiler style, every diagnostic with its location and the line it is about
+//   error: unknown action 'X'
+//    --> day_12/input.txt:3:1
+//     |
+//   3 | X10
+//     | ^ expected an action N, S, E, W, L, R or F followed by a number
+pub fn render(lints: &[Lint]) -> String {
+    let mut out = String::new();
+
+    for each_lint in lints {
+        let gutter = each_lint.diagnostics.iter().filter_map(|d| d.line).max().unwrap_or(0).to_string().len();
+
+        for each_diagnostic in &each_lint.diagnostics {
+            writeln!(out, "error: {}", each_diagnostic.message).unwrap();
+            let line = match each_diagnostic.line {
+                Some(line) => line,
+                None => {
+                    writeln!(out, "{:>w$}--> {}\n", "", each_lint.path.display(), w = gutter).unwrap();
+                    continue;
+                }
+            };
+
+            writeln!(out, "{:>w$}--> {}:{}:{}", "", each_lint.path.display(), line, each_diagnostic.column, w = gutter).unwrap();
+            writeln!(out, "{:>w$} |", "", w = gutter).unwrap();
+            writeln!(out, "{:>w$} | {}", line, each_lint.lines.get(line - 1).map_or("", |l| l.as_str()), w = gutter).unwrap();
+            let caret = format!("{}{}", " ".repeat(each_diagnostic.column - 1), "^".repeat(each_diagnostic.len));
+            match &each_diagnostic.expected {
+                Some(expected) => writeln!(out, "{:>w$} | {} expected {}", "", caret, expected, w = gutter).unwrap(),
+                None => writeln!(out, "{:>w$} | {}", "", caret, w = gutter).unwrap()
+            }
+            writeln!(out).unwrap();
+        }
+
+        match each_lint.diagnostics.len() {
+            0 => writeln!(out, "{}: no problems in {}", each_lint.puzzle_name, each_lint.path.display()).unwrap(),
+            1 => writeln!(out, "{}: 1 problem in {}", each_lint.puzzle_name, each_lint.path.display()).unwrap(),
+            n => writeln!(out, "{}: {} problems in {}", each_lint.puzzle_name, n, each_lint.path.display()).unwrap()
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::puzzles::lint::*;
+
+    #[test]
+    fn test_token_column() {
+        // given
+        let _text = "7,13,x,é,abc";
+
+        // when
+        let _res = Diagnostic::token(2, _text, &_text[10..], "invalid bus id");
+
+        // then
+        assert_eq!((_res.line, _res.column, _res.len), (Some(2), 10, 3));
+    }
+
+    #[test]
+    fn test_from_error() {
+        // given
+        let _lines = vec!["nop +0".to_string(), "mul +3".to_string()];
+        let _err = PuzzleError::parse("mul +3", "unknown operation").at_line(2);
+
+        // when
+        let _res = Diagnostic::from_error(&_lines, &_err);
+
+        // then
+        assert_eq!(_res, Diagnostic::line(2, "mul +3", "unknown operation"));
+    }
+
+    #[test]
+    fn test_render() {
+        // given
+        let _lints = vec![Lint {
+            puzzle_name: "day_12".to_string(),
+            path: PathBuf::from("day_12/input.txt"),
+            lines: vec!["F10".to_string(), "X10".to_string()],
+            diagnostics: vec![
+                Diagnostic::token(2, "X10", &"X10"[..1], "unknown action 'X'").expected("N, S, E, W, L, R or F"),
+                Diagnostic::file("no actions")
+            ]
+        }];
+
+        // when
+        let _res = render(&_lints);
+
+        // then
+        assert_eq!(_res, "\
+error: unknown action 'X'
+ --> day_12/input.txt:2:1
+  |
+2 | X10
+  | ^ expected N, S, E, W, L, R or F
+
+error: no actions
+ --> day_12/input.txt
+
+day_12: 2 problems in day_12/input.txt
+");
+        assert!(has_problems(&_lints));
+    }
+}
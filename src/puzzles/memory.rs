@@ -0,0 +1,133 @@
+// allocations made while a part ran, counted by a global allocator that is
+// only installed with the `alloc-stats` feature
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub bytes: u64,
+    // most bytes live at once on top of what was live before the part started
+    pub peak_bytes: u64
+}
+
+pub fn enabled() -> bool {
+    cfg!(feature = "alloc-stats")
+}
+
+// counters are per thread so days solved in parallel don't count each other,
+// memory freed on another thread than it was allocated on is not tracked
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell
+    };
+
+    use super::AllocStats;
+
+    thread_local! {
+        static ALLOCATIONS: Cell<u64> = const { Cell::new(0) };
+        static BYTES: Cell<u64> = const { Cell::new(0) };
+        // negative when freeing what was allocated before counting started
+        static LIVE: Cell<i64> = const { Cell::new(0) };
+        static PEAK: Cell<i64> = const { Cell::new(0) };
+    }
+
+    // the counters have no destructor, so they stay usable while a thread shuts down
+    fn record_alloc(size: usize) {
+        let _ = ALLOCATIONS.try_with(|a| a.set(a.get() + 1));
+        let _ = BYTES.try_with(|b| b.set(b.get() + size as u64));
+        let _ = LIVE.try_with(|l| {
+            l.set(l.get() + size as i64);
+            let _ = PEAK.try_with(|p| p.set(p.get().max(l.get())));
+        });
+    }
+
+    fn record_free(size: usize) {
+        let _ = LIVE.try_with(|l| l.set(l.get() - size as i64));
+    }
+
+    pub struct Counting;
+
+    unsafe impl GlobalAlloc for Counting {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                record_alloc(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            record_free(layout.size());
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                record_free(layout.size());
+                record_alloc(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: Counting = Counting;
+
+    pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, Option<AllocStats>) {
+        let (allocations, bytes, live) = (ALLOCATIONS.get(), BYTES.get(), LIVE.get());
+        PEAK.set(live);
+
+        let value = f();
+
+        let stats = AllocStats {
+            allocations: ALLOCATIONS.get() - allocations,
+            bytes: BYTES.get() - bytes,
+            peak_bytes: (PEAK.get() - live).max(0) as u64
+        };
+        (value, Some(stats))
+    }
+}
+
+// runs `f` and reports what it allocated on this thread, None without the feature
+#[cfg(feature = "alloc-stats")]
+pub use counting::measure;
+
+#[cfg(not(feature = "alloc-stats"))]
+pub fn measure<T, F: FnOnce() -> T>(f: F) -> (T, Option<AllocStats>) {
+    (f(), None)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::puzzles::memory::*;
+
+    #[test]
+    fn test_measure() {
+        // when
+        let (_res, _stats) = measure(|| {
+            let _small: Vec<u8> = Vec::with_capacity(1024);
+            let _large: Vec<u8> = Vec::with_capacity(4096);
+            _small.capacity() + _large.capacity()
+        });
+
+        // then
+        assert_eq!(_res, 5120);
+        match _stats {
+            Some(_stats) => {
+                assert_eq!(_stats.allocations, 2);
+                assert_eq!(_stats.bytes, 5120);
+                assert_eq!(_stats.peak_bytes, 5120);
+            },
+            None => assert!(!enabled())
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::{
+    fmt::{self, Display, Write},
+    str::FromStr
+};
+
+use crate::puzzles::Day;
+
+// width summaries are wrapped at in `aoc list --verbose`
+const SUMMARY_WIDTH: usize = 72;
+
+// topics a puzzle is about, used to find days solved with similar techniques
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Tag {
+    Bits,
+    DynamicProgramming,
+    Geometry,
+    Graph,
+    Grid,
+    NumberTheory,
+    Parsing,
+    Search,
+    Sets,
+    Simulation
+}
+
+impl Tag {
+    pub const ALL: [Tag; 10] = [
+        Tag::Bits, Tag::DynamicProgramming, Tag::Geometry, Tag::Graph, Tag::Grid,
+        Tag::NumberTheory, Tag::Parsing, Tag::Search, Tag::Sets, Tag::Simulation
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Tag::Bits => "bits",
+            Tag::DynamicProgramming => "dynamic-programming",
+            Tag::Geometry => "geometry",
+            Tag::Graph => "graph",
+            Tag::Grid => "grid",
+            Tag::NumberTheory => "number-theory",
+            Tag::Parsing => "parsing",
+            Tag::Search => "search",
+            Tag::Sets => "sets",
+            Tag::Simulation => "simulation"
+        }
+    }
+}
+
+impl Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Tag {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Tag, Self::Err> {
+        Tag::ALL.iter().copied().find(|t| t.name() == input).ok_or_else(|| {
+            let names: Vec<&str> = Tag::ALL.iter().map(|t| t.name()).collect();
+            format!("Invalid tag '{}', expected one of {}", input, names.join(", "))
+        })
+    }
+}
+
+// what a day is about, declared as `METADATA` next to its `Puzzle`
+#[derive(Debug)]
+pub struct Metadata {
+    pub title: &'static str,
+    pub tags: &'static [Tag],
+    pub summary: &'static str
+}
+
+impl Metadata {
+    pub fn has_tag(&self, tag: Tag) -> bool {
+        self.tags.contains(&tag)
+    }
+}
+
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+    let mut line = String::new();
+
+    for word in text.split_whitespace() {
+        if !line.is_empty() && line.len() + 1 + word.len() > width {
+            lines.push(line);
+            line = String::new();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(word);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+
+// one line per day with its title and tags, followed by the summary when `verbose`
+pub fn render(days: &[&Day], verbose: bool) -> String {
+    let mut out = String::new();
+
+    for (i, each_day) in days.iter().enumerate() {
+        let tags: Vec<&str> = each_day.metadata.tags.iter().map(|t| t.name()).collect();
+        if verbose && i > 0 {
+            writeln!(out).unwrap();
+        }
+        writeln!(out, "{:<8} {:<22} {}", each_day.name, each_day.metadata.title, tags.join(", ")).unwrap();
+        if verbose {
+            for each_line in wrap(each_day.metadata.summary, SUMMARY_WIDTH) {
+                writeln!(out, "         {}", each_line).unwrap();
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::puzzles::metadata::*;
+    use crate::puzzles::find_day;
+
+    #[test]
+    fn test_parse_tag() {
+        // then
+        assert_eq!("number-theory".parse::<Tag>(), Ok(Tag::NumberTheory));
+        assert!("graphs".parse::<Tag>().unwrap_err().starts_with("Invalid tag 'graphs', expected one of bits,"));
+        for _tag in Tag::ALL {
+            assert_eq!(_tag.name().parse::<Tag>(), Ok(_tag));
+        }
+    }
+
+    #[test]
+    fn test_wrap() {
+        // when
+        let _res = wrap("one two three four five", 9);
+
+        // then
+        assert_eq!(_res, vec!["one two", "three", "four five"]);
+    }
+
+    #[test]
+    fn test_render() {
+        // given
+        let _days = [find_day(7).unwrap(), find_day(8).unwrap()];
+
+        // when
+        let _res = render(&_days, false);
+
+        // then
+        assert_eq!(_res, "\
+day_07   Handy Haversacks       graph, parsing
+day_08   Handheld Halting       simulation, parsing
+");
+    }
+}
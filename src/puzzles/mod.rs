@@ -1,52 +1,238 @@
-use std::thread::{spawn, JoinHandle};
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc::{channel, Sender},
+        Arc
+    },
+    thread::{available_parallelism, spawn, JoinHandle},
+    time::Duration
+};
 
+pub mod cancel;
+pub mod grid;
+pub mod input;
+pub mod lint;
+pub mod memory;
+pub mod metadata;
+pub mod panics;
 pub mod solution;
-use super::puzzles::solution::{Solution, PuzzleResult};
-
-pub mod day_01;
-pub mod day_02;
-pub mod day_03;
-pub mod day_04;
-pub mod day_05;
-pub mod day_06;
-pub mod day_07;
-pub mod day_08;
-pub mod day_09;
-pub mod day_10;
-pub mod day_11;
-pub mod day_12;
-pub mod day_13;
-pub mod day_14;
-
-fn solve_puzzle<F>(_puzzle_name: &'static str, _run_solution: F) -> JoinHandle<()>
-where
-    F: Fn(&str) -> PuzzleResult + std::marker::Sync + Send + 'static
-{
-    spawn(move || {
-        println!("Starting solution for {}", _puzzle_name);
-        _run_solution(_puzzle_name).show_results();
+use super::puzzles::solution::{Solution, PuzzleResult, Part};
+use super::puzzles::input::InputSource;
+use crate::bench::{BenchOptions, BenchResult};
+use crate::error::PuzzleError;
+
+type Solver = fn(u8, &str, &[Part], &InputSource, Option<Duration>) -> PuzzleResult;
+type Bencher = fn(&str, &[Part], &InputSource, &BenchOptions) -> BenchResult;
+type Evaluator = fn(&[String], &str) -> Result<String, PuzzleError>;
+type Linter = fn(&[String]) -> Vec<lint::Diagnostic>;
+
+pub struct Day {
+    pub number: u8,
+    pub name: &'static str,
+    // title, tags and summary declared by the day module
+    pub metadata: &'static metadata::Metadata,
+    pub solve: Solver,
+    pub bench: Bencher,
+    // runs `aoc repl` commands on the typed lines
+    pub evaluate: Evaluator,
+    // day specific repl commands as (usage, description)
+    pub commands: &'static [(&'static str, &'static str)],
+    // checks an input for `aoc lint`
+    pub lint: Linter,
+    // input.txt and answers.toml compiled in with the `embed-inputs` feature
+    pub input: Option<&'static str>,
+    pub answers: Option<&'static str>
+}
+
+// "day_07" -> 7, evaluated at compile time so a badly named module fails the build
+const fn day_number(name: &str) -> u8 {
+    let bytes = name.as_bytes();
+    assert!(bytes.len() > 4, "day modules must be named day_NN");
+
+    let mut number: u8 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        if i < 4 {
+            assert!(bytes[i] == b"day_"[i], "day modules must be named day_NN");
+        } else {
+            assert!(bytes[i].is_ascii_digit(), "day modules must be named day_NN");
+            number = number * 10 + (bytes[i] - b'0');
+        }
+        i += 1;
+    }
+
+    number
+}
+
+// a file of the day's directory, by absolute path as the days! invocation
+// lives in a generated file
+#[cfg(feature = "embed-inputs")]
+macro_rules! embedded_file {
+    ($day:ident, $file:literal) => {
+        Some(include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/puzzles/", stringify!($day), "/", $file)))
+    };
+}
+
+#[cfg(not(feature = "embed-inputs"))]
+macro_rules! embedded_file {
+    ($day:ident, $file:literal) => { None };
+}
+
+// declares every day module and registers it with its number, name and solvers,
+// invoked by build.rs with every day_NN directory under src/puzzles, each one
+// declaring `Puzzle` and `METADATA`, the paths are spelled out as the invocation
+// lives in a generated file
+macro_rules! days {
+    ($($day:ident => $path:literal),* $(,)?) => {
+        $(#[path = $path] pub mod $day;)*
+
+        pub const DAYS: &[Day] = &[
+            $(Day {
+                number: day_number(stringify!($day)),
+                name: stringify!($day),
+                metadata: &$day::METADATA,
+                solve: $day::Puzzle::solve,
+                bench: $day::Puzzle::bench,
+                evaluate: $day::Puzzle::evaluate,
+                commands: $day::Puzzle::COMMANDS,
+                lint: $day::Puzzle::lint,
+                input: embedded_file!($day, "input.txt"),
+                answers: embedded_file!($day, "answers.toml")
+            }),*
+        ];
+    };
+}
+
+include!(concat!(env!("OUT_DIR"), "/days.rs"));
+
+pub fn find_day(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+pub fn embedded_input(name: &str) -> Option<&'static str> {
+    DAYS.iter().find(|day| day.name == name).and_then(|day| day.input)
+}
+
+pub fn embedded_answers(name: &str) -> Option<&'static str> {
+    DAYS.iter().find(|day| day.name == name).and_then(|day| day.answers)
+}
+
+pub fn available_days() -> Vec<u8> {
+    DAYS.iter().map(|day| day.number).collect()
+}
+
+// number of workers used when --jobs is not given
+pub fn default_jobs() -> usize {
+    available_parallelism().map_or(1, |n| n.get())
+}
+
+// worker taking the next unsolved day until none are left
+fn spawn_worker(_queue: Arc<Vec<(&'static Day, InputSource)>>, _next: Arc<AtomicUsize>, _parts: Vec<Part>, _timeout: Option<Duration>, _sender: Sender<(usize, PuzzleResult)>) -> JoinHandle<()> {
+    spawn(move || loop {
+        let _index = _next.fetch_add(1, Ordering::SeqCst);
+        let (_day, _source) = match _queue.get(_index) {
+            Some(_job) => _job,
+            None => break
+        };
+
+        let _result = (_day.solve)(_day.number, _day.name, &_parts, _source, _timeout);
+        // the receiver is only gone if the runner itself panicked
+        let _ = _sender.send((_index, _result));
     })
 }
 
-pub fn solve_all() {
-    let handles : Vec<JoinHandle<()>> = vec![
-        solve_puzzle("day_01", day_01::Puzzle::solve),
-        solve_puzzle("day_02", day_02::Puzzle::solve),
-        solve_puzzle("day_03", day_03::Puzzle::solve),
-        solve_puzzle("day_04", day_04::Puzzle::solve),
-        solve_puzzle("day_05", day_05::Puzzle::solve),
-        solve_puzzle("day_06", day_06::Puzzle::solve),
-        solve_puzzle("day_07", day_07::Puzzle::solve),
-        solve_puzzle("day_08", day_08::Puzzle::solve),
-        solve_puzzle("day_09", day_09::Puzzle::solve),
-        solve_puzzle("day_10", day_10::Puzzle::solve),
-        solve_puzzle("day_11", day_11::Puzzle::solve),
-        solve_puzzle("day_12", day_12::Puzzle::solve),
-        solve_puzzle("day_13", day_13::Puzzle::solve),
-        solve_puzzle("day_14", day_14::Puzzle::solve),
-    ];
+// every day is solved against its own input by up to `_jobs` workers, `_on_finished`
+// sees results as they complete but they are returned in the order of `_queue`
+pub fn solve_inputs<F>(_queue: Vec<(&'static Day, InputSource)>, _parts: &[Part], _jobs: usize, _timeout: Option<Duration>, mut _on_finished: F) -> Vec<PuzzleResult>
+where
+    F: FnMut(&PuzzleResult)
+{
+    let _queue = Arc::new(_queue);
+    let _next = Arc::new(AtomicUsize::new(0));
+    let (_sender, _receiver) = channel();
+
+    let handles: Vec<JoinHandle<()>> = (0.._jobs.clamp(1, _queue.len().max(1)))
+        .map(|_| spawn_worker(_queue.clone(), _next.clone(), _parts.to_vec(), _timeout, _sender.clone()))
+        .collect();
+    drop(_sender);
+
+    let mut _results: Vec<Option<PuzzleResult>> = vec![None; _queue.len()];
+    for (_index, _result) in _receiver {
+        _on_finished(&_result);
+        _results[_index] = Some(_result);
+    }
 
     for handle in handles {
         handle.join().unwrap();
     }
-}
\ No newline at end of file
+
+    _results.into_iter().flatten().collect()
+}
+
+// all days read from the same source, results are in the order of `_days`
+pub fn solve_days<F>(_days: &[u8], _parts: &[Part], _source: &InputSource, _jobs: usize, _timeout: Option<Duration>, _on_finished: F) -> Vec<PuzzleResult>
+where
+    F: FnMut(&PuzzleResult)
+{
+    // unknown days are already rejected by the cli
+    let _queue = _days.iter()
+        .filter_map(|_day| find_day(*_day))
+        .map(|_day| (_day, _source.clone()))
+        .collect();
+
+    solve_inputs(_queue, _parts, _jobs, _timeout, _on_finished)
+}
+
+// days are benchmarked one after another so they don't compete for cores
+pub fn bench_days(_days: &[u8], _parts: &[Part], _source: &InputSource, _options: &BenchOptions) -> Vec<BenchResult> {
+    _days
+        .iter()
+        .filter_map(|_day| find_day(*_day))
+        .map(|_day| {
+            eprintln!("Benchmarking {}", _day.name);
+            (_day.bench)(_day.name, _parts, _source, _options)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::puzzles::*;
+
+    #[test]
+    fn test_registry_is_ordered_and_named_by_number() {
+        for (i, _day) in DAYS.iter().enumerate() {
+            assert_eq!(_day.number as usize, i + 1);
+            assert_eq!(_day.name, format!("day_{:02}", _day.number));
+        }
+    }
+
+    #[test]
+    fn test_solve_days_keeps_requested_order() {
+        // given
+        let _source = InputSource::File(std::env::temp_dir().join("aoc_mod_test_missing.txt"));
+        let mut _finished = 0;
+
+        // when
+        let _res_pool = solve_days(&[3, 1, 2], &Part::ALL, &_source, 2, None, |_| _finished += 1);
+        let _res_sequential = solve_days(&[3, 1, 2], &Part::ALL, &_source, 1, None, |_| _finished += 1);
+
+        // then
+        assert_eq!(_res_pool.iter().map(|r| r.day).collect::<Vec<u8>>(), vec![3, 1, 2]);
+        assert_eq!(_res_sequential.iter().map(|r| r.day).collect::<Vec<u8>>(), vec![3, 1, 2]);
+        assert_eq!(_finished, 6);
+    }
+
+    #[test]
+    fn test_embedded_input() {
+        assert_eq!(embedded_input("day_01").is_some(), cfg!(feature = "embed-inputs"));
+        assert_eq!(embedded_answers("day_01").is_some(), cfg!(feature = "embed-inputs"));
+        assert!(embedded_input("day_00").is_none());
+    }
+
+    #[test]
+    fn test_find_day() {
+        assert_eq!(find_day(8).map(|d| d.name), Some("day_08"));
+        assert!(find_day(0).is_none());
+    }
+}
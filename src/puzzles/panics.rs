@@ -0,0 +1,56 @@
+use std::{
+    cell::{Cell, RefCell},
+    panic::{self, AssertUnwindSafe},
+    sync::Once
+};
+
+thread_local! {
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    static MESSAGE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// panics raised inside `catch_panic` are recorded instead of printed,
+// everything else still goes through the default hook
+fn install_hook() {
+    static HOOK: Once = Once::new();
+
+    HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if CATCHING.with(|c| c.get()) {
+                MESSAGE.with(|m| *m.borrow_mut() = Some(info.to_string().replace('\n', " ")));
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+}
+
+// runs `f`, turning a panic into its message and location
+pub fn catch_panic<T, F: FnOnce() -> T>(f: F) -> Result<T, String> {
+    install_hook();
+
+    let was_catching = CATCHING.with(|c| c.replace(true));
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.with(|c| c.set(was_catching));
+
+    result.map_err(|_| MESSAGE.with(|m| m.borrow_mut().take()).unwrap_or_else(|| "panicked".to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::puzzles::panics::*;
+
+    #[test]
+    fn test_catch_panic() {
+        // when
+        let _res_ok = catch_panic(|| 42);
+        let _res_panic: Result<u8, String> = catch_panic(|| panic!("seat {} is taken", 7));
+
+        // then
+        assert_eq!(_res_ok, Ok(42));
+        let _message = _res_panic.unwrap_err();
+        assert!(_message.starts_with("panicked at src/puzzles/panics.rs:"));
+        assert!(_message.ends_with("seat 7 is taken"));
+    }
+}
@@ -1,94 +1,576 @@
-use std::time::{Instant};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant}
+};
+
+use crate::bench::{self, BenchOptions, BenchResult};
+use crate::error::PuzzleError;
+use crate::puzzles::cancel;
+use crate::puzzles::memory::{self, AllocStats};
+use crate::puzzles::input::InputSource;
+use crate::puzzles::lint::Diagnostic;
+use crate::puzzles::panics::catch_panic;
 
 use std::{
-    fmt::Display,
-    io::{BufReader, prelude::*},
+    fmt::{self, Display},
+    io::prelude::*,
     path::Path,
-    fs::File
+    str::FromStr
 };
 
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum Part {
+    One,
+    Two
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(&self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "one"),
+            Part::Two => write!(f, "two")
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Part, Self::Err> {
+        match input {
+            "1" | "one" => Ok(Part::One),
+            "2" | "two" => Ok(Part::Two),
+            _ => Err(format!("Invalid part '{}', expected 1 or 2", input))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Solved(String),
+    Error(String),
+    // the solution panicked, holds the panic message
+    Failed(String),
+    // the part ran out of its time budget
+    Timeout(String)
+}
+
+impl Status {
+    pub fn answer(&self) -> Option<&str> {
+        match self {
+            Status::Solved(answer) => Some(answer),
+            _ => None
+        }
+    }
+
+    pub fn message(&self) -> Option<&str> {
+        match self {
+            Status::Error(message) | Status::Failed(message) | Status::Timeout(message) => Some(message),
+            _ => None
+        }
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Solved(_) => write!(f, "ok"),
+            Status::Error(_) => write!(f, "error"),
+            Status::Failed(_) => write!(f, "failed"),
+            Status::Timeout(_) => write!(f, "timeout")
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub part: Part,
+    pub status: Status,
+    pub time: Duration,
+    // only measured with the `alloc-stats` feature
+    pub alloc: Option<AllocStats>
+}
+
+#[derive(Debug, Clone)]
 pub struct PuzzleResult {
-    puzzle_name: String,
-    part_one_time_ms: String,
-    part_two_time_ms: String,
-    solution_part_one: String,
-    solution_part_two: String
+    pub day: u8,
+    pub puzzle_name: String,
+    pub parse_time: Duration,
+    pub parts: Vec<PartResult>
 }
 
 impl PuzzleResult {
-    pub fn new(puzzle_name: String, part_one_time: String, part_two_time: String, solution_p1: String, solution_p2: String) -> Self {
+    pub fn new(day: u8, puzzle_name: String) -> Self {
         Self {
+            day,
             puzzle_name,
-            part_one_time_ms: part_one_time,
-            part_two_time_ms: part_two_time,
-            solution_part_one: solution_p1,
-            solution_part_two: solution_p2
+            parse_time: Duration::ZERO,
+            parts: vec![]
         }
     }
 
-    pub fn show_results(&self) {
-        println!("\nResults for {}", self.puzzle_name);
-        println!("  * Part one:");
-        println!("       Result: {}", self.solution_part_one);
-        println!("       Execution time: {}ms", self.part_one_time_ms);
-        println!("  * Part two:");
-        println!("       Result: {}", self.solution_part_two);
-        println!("       Execution time: {}ms", self.part_two_time_ms);
+    pub fn passed(&self) -> bool {
+        self.parts.iter().all(|p| matches!(p.status, Status::Solved(_)))
+    }
+
+    pub fn add_part(&mut self, part: Part, status: Status, time: Duration) -> &mut PartResult {
+        self.parts.push(PartResult {
+            part,
+            status,
+            time,
+            alloc: None
+        });
+        self.parts.last_mut().unwrap()
     }
 }
 
+pub fn parse_lines<T, F>(input: &[String], mut parse: F) -> Result<Vec<T>, PuzzleError>
+where
+    F: FnMut(&str) -> Result<T, PuzzleError>
+{
+    input
+        .iter()
+        .enumerate()
+        .map(|(i, line)| parse(line).map_err(|e| e.at_line(i + 1)))
+        .collect()
+}
+
+// parses blank-line separated records of several lines each,
+// errors point at the first line of the failing record
+pub fn parse_records<T, F>(input: &[String], mut parse: F) -> Result<Vec<T>, PuzzleError>
+where
+    F: FnMut(&[String]) -> Result<T, PuzzleError>
+{
+    let mut records = vec![];
+    let mut start = 0;
+
+    for end in 0..=input.len() {
+        if end < input.len() && !input[end].is_empty() {
+            continue;
+        }
+        if start < end {
+            records.push(parse(&input[start..end]).map_err(|e| e.at_line(start + 1))?);
+        }
+        start = end + 1;
+    }
+
+    Ok(records)
+}
+
+// name the lines typed into `aoc repl` are reported under
+pub const REPL_SOURCE: &str = "<repl>";
+
 pub trait Solution {
     type PuzzleInput: std::str::FromStr + std::fmt::Debug;
 
+    // built once from the input lines and shared by both parts
+    type Parsed: Send + Sync + 'static;
+
     type OutputPartOne: Display;
     type OutputPartTwo: Display;
 
+    // extra `aoc repl` commands as (usage, description), handled by `command`
+    const COMMANDS: &'static [(&'static str, &'static str)] = &[];
+
     // should be overwritten if some extra actions are required on input
     fn normalize_input(_input: Vec<Self::PuzzleInput>) -> Vec<Self::PuzzleInput> {
         _input
     }
 
-    fn read_input(filename: &Path) -> Vec<Self::PuzzleInput> 
-        where 
-            <Self::PuzzleInput as std::str::FromStr>::Err: std::fmt::Debug 
+    // work both parts depend on belongs here, it is timed as parsing
+    fn parse(_input: Vec<Self::PuzzleInput>) -> Result<Self::Parsed, PuzzleError>;
+
+    fn parse_input<R: BufRead>(reader: R, filename: &Path) -> Result<Self::Parsed, PuzzleError>
+        where
+            <Self::PuzzleInput as std::str::FromStr>::Err: Display
     {
-        let f = File::open(filename)
-            .expect(&format!("Could not open file at path {:?}!", filename));
-        let f = BufReader::new(f);
+        let mut _input: Vec<Self::PuzzleInput> = vec![];
+        for (i, line) in reader.lines().enumerate() {
+            let line = line.map_err(|e| PuzzleError::io(e).at_path(filename).at_line(i + 1))?;
+            let parsed = line
+                .parse::<Self::PuzzleInput>()
+                .map_err(|e| PuzzleError::parse(&line, e).at_path(filename).at_line(i + 1))?;
+            _input.push(parsed);
+        }
 
-        Self::normalize_input(
-            f.lines()
-                .filter_map(|l| l.ok())
-                .map(|l| l.parse::<Self::PuzzleInput>().unwrap())
-                .collect()
-        )
+        Self::parse(Self::normalize_input(_input)).map_err(|e| e.at_path(filename))
     }
 
-    fn solve_part_one(_input: &Vec<Self::PuzzleInput>) -> Self::OutputPartOne;
-    fn solve_part_two(_input: &Vec<Self::PuzzleInput>) -> Self::OutputPartTwo;
+    // reports every problem of the input with its exact position, should be
+    // overwritten by days whose parser can't tell where a line went wrong
+    fn validate(_input: &[String]) -> Vec<Diagnostic> {
+        vec![]
+    }
 
-    fn solve(_day_name: &str) -> PuzzleResult
-        where 
-            <Self::PuzzleInput as std::str::FromStr>::Err: std::fmt::Debug 
+    // checks the whole input up front for `aoc lint`, the parser still runs on
+    // input the validator accepts and reports its first error
+    fn lint(_input: &[String]) -> Vec<Diagnostic>
+        where
+            <Self::PuzzleInput as std::str::FromStr>::Err: Display
     {
-        let _input: Vec<Self::PuzzleInput> = Self::read_input(
-            &std::env::current_dir().unwrap().join("src/puzzles").join(_day_name).join("input.txt")
+        let _diagnostics = Self::validate(_input);
+        if !_diagnostics.is_empty() {
+            return _diagnostics;
+        }
+
+        match Self::parse_input(_input.join("\n").as_bytes(), Path::new("")) {
+            Ok(_) => vec![],
+            Err(_err) => vec![Diagnostic::from_error(_input, &_err)]
+        }
+    }
+
+    fn solve_part_one(_input: &Self::Parsed) -> Result<Self::OutputPartOne, PuzzleError>;
+    fn solve_part_two(_input: &Self::Parsed) -> Result<Self::OutputPartTwo, PuzzleError>;
+
+    fn solve_part(_input: &Self::Parsed, _part: Part) -> Result<String, PuzzleError> {
+        match _part {
+            Part::One => Self::solve_part_one(_input).map(|s| s.to_string()),
+            Part::Two => Self::solve_part_two(_input).map(|s| s.to_string())
+        }
+    }
+
+    // runs one of `COMMANDS` against the parsed repl buffer
+    fn command(_input: &Self::Parsed, _name: &str, _args: &[&str]) -> Result<String, PuzzleError> {
+        Err(PuzzleError::solve(format!("':{}' is not a command of this day", _name)))
+    }
+
+    // parses the lines typed into `aoc repl` and runs `_command` on them,
+    // "one" and "two" solve a part, anything else goes to `command`
+    fn evaluate(_lines: &[String], _command: &str) -> Result<String, PuzzleError>
+        where
+            <Self::PuzzleInput as std::str::FromStr>::Err: Display
+    {
+        let _input = Self::parse_input(_lines.join("\n").as_bytes(), Path::new(REPL_SOURCE))?;
+
+        let _words: Vec<&str> = _command.split_whitespace().collect();
+        match _words[..] {
+            ["one"] => Self::solve_part(&_input, Part::One),
+            ["two"] => Self::solve_part(&_input, Part::Two),
+            [_name, ref _args @ ..] => Self::command(&_input, _name, _args),
+            [] => Err(PuzzleError::solve("missing command, see :help"))
+        }
+    }
+
+    // with a `_timeout` every part runs on its own thread, a part that doesn't
+    // finish in time is reported as a timeout and cancelled through `cancel::check`
+    fn solve(_day: u8, _day_name: &str, _parts: &[Part], _source: &InputSource, _timeout: Option<Duration>) -> PuzzleResult
+        where
+            Self: 'static,
+            <Self::PuzzleInput as std::str::FromStr>::Err: Display
+    {
+        let mut _result = PuzzleResult::new(_day, (*_day_name).to_string());
+
+        let _input = match _source.read(_day_name) {
+            Ok((_path, _text)) => {
+                let now = Instant::now();
+                let _parsed = catch_panic(|| Self::parse_input(_text.as_bytes(), &_path));
+                _result.parse_time = now.elapsed();
+
+                match _parsed {
+                    Ok(Ok(_input)) => Ok((_input, _path)),
+                    Ok(Err(_err)) => Err(Status::Error(_err.in_day(_day_name).to_string())),
+                    Err(_panic) => Err(Status::Failed(format!("{}: {}", _day_name, _panic)))
+                }
+            },
+            Err(_err) => Err(Status::Error(_err.in_day(_day_name).to_string()))
+        };
+
+        let (_input, _path) = match _input {
+            Ok(_input) => _input,
+            Err(_status) => {
+                for _part in _parts {
+                    _result.add_part(*_part, _status.clone(), Duration::ZERO);
+                }
+                return _result;
+            }
+        };
+
+        // a panicking part fails on its own, other parts still get solved
+        let _input = Arc::new(_input);
+        for _part in _parts {
+            let now = Instant::now();
+            // allocations are counted on the thread the part runs on
+            let _solution = match _timeout {
+                None => Some(catch_panic(|| memory::measure(|| Self::solve_part(&_input, *_part)))),
+                Some(_limit) => {
+                    let (_input, _part) = (_input.clone(), *_part);
+                    cancel::run_with_timeout(_limit, move || catch_panic(|| memory::measure(|| Self::solve_part(&_input, _part))))
+                }
+            };
+            let _time = now.elapsed();
+
+            let (_status, _alloc) = match _solution {
+                Some(Ok((Ok(_answer), _alloc))) => (Status::Solved(_answer), _alloc),
+                Some(Ok((Err(_err), _alloc))) => (Status::Error(_err.at_path(&_path).in_day(_day_name).to_string()), _alloc),
+                Some(Err(_panic)) => (Status::Failed(format!("{}: {}", _day_name, _panic)), None),
+                None => (Status::Timeout(format!(
+                    "{}: part {} did not finish within {:?}", _day_name, _part, _timeout.unwrap_or_default()
+                )), None)
+            };
+            _result.add_part(*_part, _status, _time).alloc = _alloc;
+        }
+
+        _result
+    }
+
+    // runs parsing and each part repeatedly, input is only read once, every stage
+    // runs on its own thread and is given up on after `_options.timeout`
+    fn bench(_day_name: &str, _parts: &[Part], _source: &InputSource, _options: &BenchOptions) -> BenchResult
+        where
+            Self: 'static,
+            Self::PuzzleInput: Send + Sync + 'static,
+            <Self::PuzzleInput as std::str::FromStr>::Err: Display
+    {
+        let mut _result = BenchResult::new((*_day_name).to_string());
+
+        let (_path, _text) = match _source.read(_day_name) {
+            Ok(_read) => _read,
+            Err(_err) => {
+                _result.add_stage("parse", Err(_err.in_day(_day_name).to_string()));
+                return _result;
+            }
+        };
+
+        let _options = *_options;
+        let _timed_out = |_stage: &str| format!("{}: {} did not finish within {:?}", _day_name, _stage, _options.timeout);
+
+        // a panicking stage fails on its own like it does when solving
+        let _parse_path = _path.clone();
+        let _parsed = cancel::run_with_timeout(_options.timeout, move || catch_panic(|| {
+            let _parse = || Self::parse_input(_text.as_bytes(), &_parse_path);
+            bench::measure(&_options, _parse).and_then(|_stats| Ok((_stats, _parse()?)))
+        }));
+        let _input = match _parsed {
+            Some(Ok(Ok((_stats, _input)))) => {
+                _result.add_stage("parse", Ok(_stats));
+                Arc::new(_input)
+            },
+            Some(Ok(Err(_err))) => {
+                _result.add_stage("parse", Err(_err.in_day(_day_name).to_string()));
+                return _result;
+            },
+            Some(Err(_panic)) => {
+                _result.add_stage("parse", Err(format!("{}: {}", _day_name, _panic)));
+                return _result;
+            },
+            None => {
+                _result.add_stage("parse", Err(_timed_out("parsing")));
+                return _result;
+            }
+        };
+
+        for _part in _parts {
+            let (_input, _part) = (_input.clone(), *_part);
+            let _stats = cancel::run_with_timeout(_options.timeout, move || catch_panic(|| match _part {
+                Part::One => bench::measure(&_options, || Self::solve_part_one(&_input)),
+                Part::Two => bench::measure(&_options, || Self::solve_part_two(&_input))
+            }));
+
+            let _stage = format!("part {}", _part);
+            _result.add_stage(
+                &_stage,
+                match _stats {
+                    Some(Ok(_stats)) => _stats.map_err(|_err| _err.at_path(&_path).in_day(_day_name).to_string()),
+                    Some(Err(_panic)) => Err(format!("{}: {}", _day_name, _panic)),
+                    None => Err(_timed_out(&_stage))
+                }
+            );
+        }
+
+        _result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::puzzles::solution::*;
+
+    struct Numbers {}
+
+    impl Solution for Numbers {
+        type PuzzleInput = u32;
+        type Parsed = Vec<u32>;
+        type OutputPartOne = u32;
+        type OutputPartTwo = u32;
+
+        fn parse(_input: Vec<Self::PuzzleInput>) -> Result<Self::Parsed, PuzzleError> {
+            Ok(_input)
+        }
+
+        fn solve_part_one(_input: &Self::Parsed) -> Result<Self::OutputPartOne, PuzzleError> {
+            Ok(_input.iter().sum())
+        }
+
+        fn solve_part_two(_input: &Self::Parsed) -> Result<Self::OutputPartTwo, PuzzleError> {
+            Ok(_input.iter().product())
+        }
+    }
+
+    struct Panicky {}
+
+    impl Solution for Panicky {
+        type PuzzleInput = u32;
+        type Parsed = Vec<u32>;
+        type OutputPartOne = u32;
+        type OutputPartTwo = u32;
+
+        fn parse(_input: Vec<Self::PuzzleInput>) -> Result<Self::Parsed, PuzzleError> {
+            Ok(_input)
+        }
+
+        fn solve_part_one(_input: &Self::Parsed) -> Result<Self::OutputPartOne, PuzzleError> {
+            Ok(_input[0])
+        }
+
+        fn solve_part_two(_input: &Self::Parsed) -> Result<Self::OutputPartTwo, PuzzleError> {
+            Ok(_input[1])
+        }
+    }
+
+    struct Endless {}
+
+    impl Solution for Endless {
+        type PuzzleInput = u32;
+        type Parsed = Vec<u32>;
+        type OutputPartOne = u32;
+        type OutputPartTwo = u32;
+
+        fn parse(_input: Vec<Self::PuzzleInput>) -> Result<Self::Parsed, PuzzleError> {
+            Ok(_input)
+        }
+
+        fn solve_part_one(_input: &Self::Parsed) -> Result<Self::OutputPartOne, PuzzleError> {
+            Ok(1)
+        }
+
+        fn solve_part_two(_input: &Self::Parsed) -> Result<Self::OutputPartTwo, PuzzleError> {
+            loop {
+                cancel::check()?;
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_records() {
+        // given
+        let _input = vec!["a b", "c", "", "", "d", ""].into_iter().map(String::from).collect::<Vec<String>>();
+
+        // when
+        let _res = parse_records(&_input, |_record| Ok(_record.join("|")));
+        let _res_err = parse_records(&_input, |_record| match _record.len() {
+            1 => Err(PuzzleError::parse(&_record[0], "single line record")),
+            _ => Ok(())
+        });
+
+        // then
+        assert_eq!(_res.unwrap(), vec!["a b|c".to_string(), "d".to_string()]);
+        assert_eq!(_res_err.unwrap_err().to_string(), "line 5: invalid input, single line record in 'd'");
+    }
+
+    #[test]
+    fn test_parse_input_reports_line() {
+        // given
+        let _path = Path::new("numbers.txt");
+        let _reader = "1\n2\nthree\n".as_bytes();
+
+        // when
+        let _res = Numbers::parse_input(_reader, _path);
+
+        // then
+        assert_eq!(
+            _res.unwrap_err().to_string(),
+            "numbers.txt:3: invalid input, invalid digit found in string in 'three'"
         );
+    }
 
-        let now = Instant::now();
-        let solution_part_one = Self::solve_part_one(&_input);
-        let p1_time = now.elapsed().as_millis();
+    #[test]
+    fn test_solve_missing_file() {
+        // given
+        let _path = std::env::temp_dir().join("aoc_solution_test_missing.txt");
 
-        let now = Instant::now();
-        let solution_part_two = Self::solve_part_two(&_input);
-        let p2_time = now.elapsed().as_millis();
+        // when
+        let _res = Numbers::solve(0, "day_00", &Part::ALL, &InputSource::File(_path.clone()), None);
 
-        PuzzleResult::new(
-            (*_day_name).to_string(), 
-            p1_time.to_string(), 
-            p2_time.to_string(), 
-            solution_part_one.to_string(), 
-            solution_part_two.to_string() 
-        )
+        // then
+        assert_eq!(_res.parts.len(), 2);
+        for _part in &_res.parts {
+            assert!(_part.status.message().unwrap().starts_with(&format!("day_00: {}: ", _path.display())));
+        }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_solve_isolates_panicking_part() {
+        // given
+        let _path = std::env::temp_dir().join("aoc_solution_test_panicky.txt");
+        std::fs::write(&_path, "7\n").unwrap();
+
+        // when
+        let _res = Panicky::solve(0, "day_00", &Part::ALL, &InputSource::File(_path.clone()), None);
+
+        // then
+        assert_eq!(_res.parts[0].status, Status::Solved("7".to_string()));
+        assert_eq!(_res.parts[1].status.to_string(), "failed");
+        assert!(_res.parts[1].status.message().unwrap().starts_with("day_00: panicked at "));
+        assert!(!_res.passed());
+        std::fs::remove_file(&_path).unwrap();
+    }
+
+    #[test]
+    fn test_bench_isolates_panicking_part() {
+        // given
+        let _path = std::env::temp_dir().join("aoc_solution_test_bench_panicky.txt");
+        std::fs::write(&_path, "7\n").unwrap();
+        let _options = BenchOptions { iterations: 2, warmup: 0, ..BenchOptions::default() };
+
+        // when
+        let _res = Panicky::bench("day_00", &Part::ALL, &InputSource::File(_path.clone()), &_options);
+
+        // then
+        assert_eq!(_res.stages.len(), 3);
+        assert!(_res.stages[1].stats.is_ok());
+        assert!(_res.stages[2].stats.as_ref().unwrap_err().starts_with("day_00: panicked at "));
+        std::fs::remove_file(&_path).unwrap();
+    }
+
+    #[test]
+    fn test_bench_times_out_part() {
+        // given
+        let _path = std::env::temp_dir().join("aoc_solution_test_bench_endless.txt");
+        std::fs::write(&_path, "7\n").unwrap();
+        let _options = BenchOptions { iterations: 2, warmup: 0, timeout: Duration::from_millis(50), ..BenchOptions::default() };
+
+        // when
+        let _res = Endless::bench("day_00", &Part::ALL, &InputSource::File(_path.clone()), &_options);
+
+        // then
+        assert!(_res.stages[1].stats.is_ok());
+        assert_eq!(_res.stages[2].stats, Err("day_00: part two did not finish within 50ms".to_string()));
+        std::fs::remove_file(&_path).unwrap();
+    }
+
+    #[test]
+    fn test_solve_times_out_part() {
+        // given
+        let _path = std::env::temp_dir().join("aoc_solution_test_endless.txt");
+        std::fs::write(&_path, "7\n").unwrap();
+
+        // when
+        let _res = Endless::solve(0, "day_00", &Part::ALL, &InputSource::File(_path.clone()), Some(Duration::from_millis(50)));
+
+        // then
+        assert_eq!(_res.parts[0].status, Status::Solved("1".to_string()));
+        assert_eq!(_res.parts[1].status, Status::Timeout("day_00: part two did not finish within 50ms".to_string()));
+        std::fs::remove_file(&_path).unwrap();
+    }
+}
@@ -0,0 +1,181 @@
+use std::{
+    fmt::Write as _,
+    io::{self, BufRead, Write},
+    time::Duration
+};
+
+use crate::puzzles::{cancel::{self, DEFAULT_TIMEOUT}, input::InputSource, panics::catch_panic, Day};
+
+const BUILTIN_COMMANDS: [(&str, &str); 8] = [
+    ("one", "solve part one on the lines typed so far"),
+    ("two", "solve part two on the lines typed so far"),
+    ("show", "print the lines typed so far"),
+    ("undo", "remove the last line"),
+    ("clear", "remove all lines"),
+    ("load [FILE]", "add the lines of FILE, or of the day's input"),
+    ("help", "print this message"),
+    ("quit", "leave the repl, as does end of input")
+];
+
+// lines typed into `aoc repl`, solved from scratch on every command
+pub struct Session {
+    day: &'static Day,
+    pub lines: Vec<String>,
+    pub timeout: Duration
+}
+
+impl Session {
+    pub fn new(day: &'static Day) -> Self {
+        // a slow part must not lock the session
+        Self { day, lines: vec![], timeout: DEFAULT_TIMEOUT }
+    }
+
+    pub fn help(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "lines not starting with ':' are added to the input of {}", self.day.name).unwrap();
+        for (usage, description) in BUILTIN_COMMANDS.iter().chain(self.day.commands) {
+            writeln!(out, "    :{:<15} {}", usage, description).unwrap();
+        }
+        out
+    }
+
+    // the part commands and the day's own, by name
+    fn is_solver_command(&self, name: &str) -> bool {
+        name == "one" || name == "two" || self.day.commands.iter().any(|(usage, _)| usage.split(' ').next() == Some(name))
+    }
+
+    // solved on its own thread, the lines are copied so the session stays usable
+    // when the command is given up on
+    fn evaluate(&self, command: &str) -> String {
+        let (evaluate, lines, owned_command) = (self.day.evaluate, self.lines.clone(), command.to_string());
+        match cancel::run_with_timeout(self.timeout, move || catch_panic(|| evaluate(&lines, &owned_command))) {
+            Some(Ok(Ok(answer))) => format!("{}\n", answer),
+            Some(Ok(Err(err))) => format!("error: {}\n", err),
+            Some(Err(panic)) => format!("failed: {}\n", panic),
+            None => format!("error: ':{}' did not finish within {:?}\n", command, self.timeout)
+        }
+    }
+
+    fn load(&mut self, file: Option<&str>) -> String {
+        let source = file.map_or(InputSource::Default, InputSource::from_arg);
+        match source.read(self.day.name) {
+            Ok((path, text)) => {
+                let count = text.lines().count();
+                self.lines.extend(text.lines().map(String::from));
+                format!("added {} lines from {}\n", count, path.display())
+            },
+            Err(err) => format!("error: {}\n", err)
+        }
+    }
+
+    // handles one line of input, None once the session should end
+    pub fn handle(&mut self, line: &str) -> Option<String> {
+        let command = match line.strip_prefix(':') {
+            Some(command) => command.trim(),
+            None => {
+                self.lines.push(line.to_string());
+                return Some(String::new());
+            }
+        };
+
+        let out = match command.split_once(' ').map_or((command, ""), |(name, arg)| (name, arg.trim())) {
+            ("quit", _) | ("q", _) => return None,
+            ("help", _) | ("h", _) => self.help(),
+            ("show", _) => self.lines.iter().enumerate().map(|(i, l)| format!("{:>4} | {}\n", i + 1, l)).collect(),
+            ("undo", _) => match self.lines.pop() {
+                Some(removed) => format!("removed '{}'\n", removed),
+                None => "nothing to remove\n".to_string()
+            },
+            ("clear", _) => {
+                self.lines.clear();
+                "cleared\n".to_string()
+            },
+            ("load", file) => self.load(Some(file).filter(|f| !f.is_empty())),
+            (name, _) if !self.is_solver_command(name) => format!("unknown command ':{}', see :help\n", name),
+            _ => self.evaluate(command)
+        };
+        Some(out)
+    }
+}
+
+// reads lines from `input` until :quit or end of input, the prompt is only
+// printed when a person is typing
+pub fn run<R: BufRead, W: Write>(day: &'static Day, input: R, out: &mut W, prompt: bool) -> io::Result<()> {
+    let mut session = Session::new(day);
+    let prompt = if prompt { format!("{}> ", day.name) } else { String::new() };
+
+    write!(out, "{}{}", session.help(), prompt)?;
+    out.flush()?;
+    for line in input.lines() {
+        match session.handle(&line?) {
+            Some(text) => write!(out, "{}{}", text, prompt)?,
+            None => break
+        }
+        out.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::puzzles::find_day;
+    use crate::repl::*;
+
+    #[test]
+    fn test_session() {
+        // given
+        let mut _session = Session::new(find_day(5).unwrap());
+
+        // when
+        _session.handle("FBFBBFFRLR");
+        _session.handle("BFFFBBFRRR");
+        let _res_one = _session.handle(":one");
+        let _res_seat = _session.handle(":seat FFFBBBFRRR");
+        _session.handle("nonsense");
+        let _res_error = _session.handle(":one");
+        let _res_undo = _session.handle(":undo");
+        let _res_unknown = _session.handle(":fly");
+        let _res_quit = _session.handle(":quit");
+
+        // then
+        assert_eq!(_res_one.unwrap(), "567\n");
+        assert_eq!(_res_seat.unwrap(), "row 14, column 7, seat ID 119\n");
+        assert_eq!(_res_error.unwrap(), "error: <repl>:3: invalid input, expected 10 characters boarding pass in 'nonsense'\n");
+        assert_eq!(_res_undo.unwrap(), "removed 'nonsense'\n");
+        assert_eq!(_res_unknown.unwrap(), "unknown command ':fly', see :help\n");
+        assert_eq!(_res_quit, None);
+        assert_eq!(_session.lines, vec!["FBFBBFFRLR", "BFFFBBFRRR"]);
+    }
+
+    #[test]
+    fn test_session_timeout() {
+        // given
+        let mut _session = Session::new(find_day(13).unwrap());
+        _session.timeout = Duration::from_millis(50);
+        _session.handle("1");
+        _session.handle("1000003,1000033,1000037");
+
+        // when
+        let _res_two = _session.handle(":two");
+        let _res_one = _session.handle(":one");
+
+        // then
+        assert_eq!(_res_two.unwrap(), "error: ':two' did not finish within 50ms\n");
+        assert_eq!(_res_one.unwrap(), "1000005000006\n");
+    }
+
+    #[test]
+    fn test_run() {
+        // given
+        let _input = "nop +0\nacc +1\njmp -2\n:one\n:quit\n:two\n";
+        let mut _out: Vec<u8> = vec![];
+
+        // when
+        run(find_day(8).unwrap(), _input.as_bytes(), &mut _out, false).unwrap();
+
+        // then
+        let _out = String::from_utf8(_out).unwrap();
+        assert!(_out.contains("    :trace           run the program step by step until it ends or loops\n"));
+        assert!(_out.ends_with("1\n"));
+    }
+}
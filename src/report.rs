@@ -0,0 +1,285 @@
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration
+};
+
+use crate::output::format_duration;
+use crate::puzzles::find_day;
+use crate::puzzles::solution::{Part, PuzzleResult, Status};
+use crate::verify::{Check, Verdict};
+
+const TITLE: &str = "Advent of Code 2020";
+// characters of the longest bar in the Markdown table
+const BAR_WIDTH: usize = 20;
+const MASK: &str = "\u{2022}\u{2022}\u{2022}\u{2022}\u{2022}\u{2022}";
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ReportFormat {
+    Markdown,
+    Html
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<ReportFormat, Self::Err> {
+        match input {
+            "markdown" | "md" => Ok(ReportFormat::Markdown),
+            "html" => Ok(ReportFormat::Html),
+            _ => Err(format!("Invalid report format '{}', expected markdown or html", input))
+        }
+    }
+}
+
+impl ReportFormat {
+    // report.html is a page, anything else a Markdown table
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("html") | Some("htm") => ReportFormat::Html,
+            _ => ReportFormat::Markdown
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReportOptions {
+    pub format: ReportFormat,
+    // hide answers so the report can be shared without spoilers
+    pub mask: bool,
+    // printed to stdout when None
+    pub output: Option<PathBuf>
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        Self {
+            format: ReportFormat::Markdown,
+            mask: false,
+            output: None
+        }
+    }
+}
+
+// one part of one day as it appears in the report
+struct Row {
+    puzzle_name: String,
+    // puzzle title from the day's metadata
+    title: &'static str,
+    part: Part,
+    answer: String,
+    verdict: String,
+    passed: bool,
+    parse_time: Duration,
+    time: Duration,
+    // time relative to the slowest part, between 0 and 1
+    share: f64
+}
+
+fn rows(results: &[PuzzleResult], checks: &[Check], mask: bool) -> Vec<Row> {
+    let slowest = results
+        .iter()
+        .flat_map(|r| r.parts.iter().map(|p| p.time))
+        .max()
+        .unwrap_or_default();
+
+    results
+        .iter()
+        .flat_map(|result| result.parts.iter().map(move |each_part| {
+            let answer = match &each_part.status {
+                Status::Solved(_) if mask => MASK.to_string(),
+                Status::Solved(answer) => answer.clone(),
+                status => status.to_string()
+            };
+
+            let verdict = checks
+                .iter()
+                .find(|c| c.puzzle_name == result.puzzle_name && c.part == each_part.part)
+                .map_or(Verdict::Missing, |c| c.verdict.clone());
+            // a wrong answer is explained with both answers, which masking has to hide
+            let verdict_text = match (&verdict, &each_part.status) {
+                (Verdict::Fail(_), Status::Solved(_)) if mask => verdict.to_string(),
+                (Verdict::Fail(reason), _) => format!("{}: {}", verdict, reason),
+                _ => verdict.to_string()
+            };
+
+            Row {
+                puzzle_name: result.puzzle_name.clone(),
+                title: find_day(result.day).map_or("", |d| d.metadata.title),
+                part: each_part.part,
+                answer,
+                verdict: verdict_text,
+                passed: verdict == Verdict::Pass,
+                parse_time: result.parse_time,
+                time: each_part.time,
+                share: if slowest.is_zero() { 0.0 } else { each_part.time.as_secs_f64() / slowest.as_secs_f64() }
+            }
+        }))
+        .collect()
+}
+
+fn summary(rows: &[Row], results: &[PuzzleResult]) -> String {
+    let passed = rows.iter().filter(|r| r.passed).count();
+    let total: Duration = results.iter().map(|r| r.parse_time + r.parts.iter().map(|p| p.time).sum::<Duration>()).sum();
+    format!("{} of {} parts verified, {} in total", passed, rows.len(), format_duration(total))
+}
+
+// answers, timings and verdicts of every part, verdicts come from verify::verify
+pub fn render(results: &[PuzzleResult], checks: &[Check], format: ReportFormat, mask: bool) -> String {
+    let rows = rows(results, checks, mask);
+    match format {
+        ReportFormat::Markdown => render_markdown(&rows, &summary(&rows, results)),
+        ReportFormat::Html => render_html(&rows, &summary(&rows, results))
+    }
+}
+
+fn escape_markdown(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
+
+// drawn in eighths of a block for a finer resolution than whole characters
+fn text_bar(share: f64) -> String {
+    const PARTIAL: [char; 8] = [' ', '▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+
+    let eighths = (share * (BAR_WIDTH * 8) as f64).round() as usize;
+    let (full, partial) = (eighths / 8, eighths % 8);
+    let mut bar = "█".repeat(full);
+    if partial > 0 {
+        bar.push(PARTIAL[partial]);
+    }
+    bar
+}
+
+fn render_markdown(rows: &[Row], summary: &str) -> String {
+    let mut out = String::new();
+
+    writeln!(out, "# {} results\n", TITLE).unwrap();
+    writeln!(out, "| Day | Title | Part | Answer | Verification | Parse | Time | |").unwrap();
+    writeln!(out, "|-----|-------|------|--------|--------------|------:|-----:|-|").unwrap();
+    for each_row in rows {
+        writeln!(
+            out, "| {} | {} | {} | {} | {} | {} | {} | {} |",
+            each_row.puzzle_name, each_row.title, each_row.part, escape_markdown(&each_row.answer), escape_markdown(&each_row.verdict),
+            format_duration(each_row.parse_time), format_duration(each_row.time), text_bar(each_row.share)
+        ).unwrap();
+    }
+    writeln!(out, "\n{}", summary).unwrap();
+
+    out
+}
+
+fn escape_html(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            c => out.push(c)
+        }
+    }
+    out
+}
+
+const STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; }
+table { border-collapse: collapse; }
+th, td { padding: 0.3em 0.8em; border-bottom: 1px solid #ddd; text-align: left; }
+td.time { text-align: right; font-family: monospace; }
+td.pass { color: #2a7d2a; }
+td.fail { color: #b22222; }
+td.chart { width: 12em; }
+div.bar { height: 0.8em; background: #4a7bd0; }";
+
+// standalone page, the bars are plain divs so it needs no scripts
+fn render_html(rows: &[Row], summary: &str) -> String {
+    let mut out = String::new();
+
+    writeln!(out, "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">").unwrap();
+    writeln!(out, "<title>{} results</title>\n<style>\n{}\n</style>\n</head>\n<body>", TITLE, STYLE).unwrap();
+    writeln!(out, "<h1>{} results</h1>\n<table>", TITLE).unwrap();
+    writeln!(out, "<tr><th>Day</th><th>Title</th><th>Part</th><th>Answer</th><th>Verification</th><th>Parse</th><th>Time</th><th></th></tr>").unwrap();
+    for each_row in rows {
+        writeln!(
+            out,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td class=\"{}\">{}</td><td class=\"time\">{}</td><td class=\"time\">{}</td>\
+             <td class=\"chart\"><div class=\"bar\" style=\"width: {:.1}%\"></div></td></tr>",
+            each_row.puzzle_name, escape_html(each_row.title), each_row.part, escape_html(&each_row.answer),
+            if each_row.passed { "pass" } else { "fail" }, escape_html(&each_row.verdict),
+            format_duration(each_row.parse_time), format_duration(each_row.time), each_row.share * 100.0
+        ).unwrap();
+    }
+    writeln!(out, "</table>\n<p>{}</p>\n</body>\n</html>", escape_html(summary)).unwrap();
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::*;
+
+    fn results() -> (Vec<PuzzleResult>, Vec<Check>) {
+        let mut _result = PuzzleResult::new(1, "day_01".to_string());
+        _result.parse_time = Duration::from_micros(20);
+        _result.add_part(Part::One, Status::Solved("514579".to_string()), Duration::from_micros(100));
+        _result.add_part(Part::Two, Status::Solved("241861950".to_string()), Duration::from_micros(400));
+
+        let _check = |part, verdict| Check { puzzle_name: "day_01".to_string(), part, answer: None, verdict };
+        let _checks = vec![
+            _check(Part::One, Verdict::Pass),
+            _check(Part::Two, Verdict::Fail("expected 241861951, got 241861950".to_string()))
+        ];
+        (vec![_result], _checks)
+    }
+
+    #[test]
+    fn test_render_markdown() {
+        // given
+        let (_results, _checks) = results();
+
+        // when
+        let _res = render(&_results, &_checks, ReportFormat::Markdown, false);
+        let _res_masked = render(&_results, &_checks, ReportFormat::Markdown, true);
+
+        // then
+        assert_eq!(_res, "\
+# Advent of Code 2020 results
+
+| Day | Title | Part | Answer | Verification | Parse | Time | |
+|-----|-------|------|--------|--------------|------:|-----:|-|
+| day_01 | Report Repair | one | 514579 | PASS | 20.00µs | 100.00µs | █████ |
+| day_01 | Report Repair | two | 241861950 | FAIL: expected 241861951, got 241861950 | 20.00µs | 400.00µs | ████████████████████ |
+
+1 of 2 parts verified, 520.00µs in total
+");
+        assert!(!_res_masked.contains("514579"));
+        assert!(!_res_masked.contains("241861950"));
+        assert!(_res_masked.contains("| FAIL |"));
+    }
+
+    #[test]
+    fn test_render_html() {
+        // given
+        let (_results, _checks) = results();
+
+        // when
+        let _res = render(&_results, &_checks, ReportFormat::Html, false);
+
+        // then
+        assert!(_res.starts_with("<!DOCTYPE html>"));
+        assert!(_res.contains("<td class=\"pass\">PASS</td>"));
+        assert!(_res.contains("<div class=\"bar\" style=\"width: 25.0%\"></div>"));
+        assert!(_res.trim_end().ends_with("</html>"));
+    }
+
+    #[test]
+    fn test_text_bar() {
+        // then
+        assert_eq!(text_bar(0.0), "");
+        assert_eq!(text_bar(1.0 / 160.0), "▏");
+        assert_eq!(text_bar(0.5), "██████████");
+        assert_eq!(text_bar(0.1 + 4.0 / 160.0), "██▌");
+    }
+}
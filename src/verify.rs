@@ -0,0 +1,223 @@
+use std::{
+    fmt::{self, Display, Write},
+    fs,
+    io,
+    path::Path
+};
+
+use crate::error::PuzzleError;
+use crate::puzzles::{self, input::InputSource};
+use crate::puzzles::solution::{Part, PuzzleResult, Status};
+
+pub const ANSWERS_FILE_NAME: &str = "answers.toml";
+
+// known-good answers, stored next to the input they belong to as
+//   part_one = "494475"
+//   part_two = "267520550"
+#[derive(Debug, Default, PartialEq)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>
+}
+
+impl Answers {
+    pub fn parse(text: &str, path: &Path) -> Result<Self, PuzzleError> {
+        let mut answers = Answers::default();
+
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let err = |reason: &str| PuzzleError::parse(line, reason).at_path(path).at_line(i + 1);
+            let (key, value) = line.split_once('=').ok_or_else(|| err("expected 'key = value'"))?;
+
+            let value = value.trim();
+            let value = match value.strip_prefix('"').and_then(|v| v.strip_suffix('"')) {
+                Some(quoted) => quoted,
+                None if !value.is_empty() && value.chars().all(|c| c.is_ascii_digit() || c == '-') => value,
+                None => return Err(err("expected a quoted string or a number"))
+            };
+
+            match key.trim() {
+                "part_one" => answers.part_one = Some(value.to_string()),
+                "part_two" => answers.part_two = Some(value.to_string()),
+                _ => return Err(err("expected key part_one or part_two"))
+            }
+        }
+
+        Ok(answers)
+    }
+
+    // a missing file is an error, nothing could be verified without it
+    pub fn load(path: &Path) -> Result<Self, PuzzleError> {
+        match fs::read_to_string(path) {
+            Ok(text) => Self::parse(&text, path),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                Err(PuzzleError::io(io::Error::new(e.kind(), "no answers file")).at_path(path))
+            },
+            Err(e) => Err(PuzzleError::io(e).at_path(path))
+        }
+    }
+
+    pub fn get(&self, part: Part) -> Option<&str> {
+        match part {
+            Part::One => self.part_one.as_deref(),
+            Part::Two => self.part_two.as_deref()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Pass,
+    Fail(String),
+    Missing
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Fail(_) => write!(f, "FAIL"),
+            Verdict::Missing => write!(f, "MISSING")
+        }
+    }
+}
+
+pub struct Check {
+    pub puzzle_name: String,
+    pub part: Part,
+    pub answer: Option<String>,
+    pub verdict: Verdict
+}
+
+fn check(status: &Status, expected: Option<&str>) -> Verdict {
+    match (status, expected) {
+        (Status::Error(message) | Status::Failed(message) | Status::Timeout(message), _) => Verdict::Fail(message.clone()),
+        (Status::Solved(_), None) => Verdict::Missing,
+        (Status::Solved(answer), Some(expected)) if answer == expected => Verdict::Pass,
+        (Status::Solved(answer), Some(expected)) => Verdict::Fail(format!("expected {}, got {}", expected, answer))
+    }
+}
+
+// answers next to the input `source` reads, an input embedded with the
+// `embed-inputs` feature comes with its answers embedded too
+fn answers_for(source: &InputSource, day_name: &str) -> Result<Answers, PuzzleError> {
+    let input_path = source.path(day_name);
+    match puzzles::embedded_answers(day_name) {
+        Some(text) if *source == InputSource::Default && !input_path.is_file() => {
+            Answers::parse(text, Path::new(&format!("<embedded {}/{}>", day_name, ANSWERS_FILE_NAME)))
+        },
+        _ => Answers::load(&input_path.with_file_name(ANSWERS_FILE_NAME))
+    }
+}
+
+pub fn verify(results: &[PuzzleResult], source: &InputSource) -> Vec<Check> {
+    let mut checks = vec![];
+
+    for result in results {
+        let answers = answers_for(source, &result.puzzle_name).map_err(|e| e.in_day(&result.puzzle_name).to_string());
+
+        for each_part in &result.parts {
+            let verdict = match &answers {
+                Ok(answers) => check(&each_part.status, answers.get(each_part.part)),
+                Err(message) => Verdict::Fail(message.clone())
+            };
+            checks.push(Check {
+                puzzle_name: result.puzzle_name.clone(),
+                part: each_part.part,
+                answer: each_part.status.answer().map(String::from),
+                verdict
+            });
+        }
+    }
+
+    checks
+}
+
+pub fn has_failures(checks: &[Check]) -> bool {
+    checks.iter().any(|c| matches!(c.verdict, Verdict::Fail(_)))
+}
+
+pub fn render(checks: &[Check]) -> String {
+    let mut out = String::new();
+    let count = |verdict: &str| checks.iter().filter(|c| c.verdict.to_string() == verdict).count();
+
+    for each_check in checks {
+        let detail = match &each_check.verdict {
+            Verdict::Fail(reason) => reason.clone(),
+            _ => each_check.answer.clone().unwrap_or_default()
+        };
+        writeln!(
+            out, "{:<8} part {:<4} {:<8} {}",
+            each_check.puzzle_name, each_check.part, each_check.verdict, detail
+        ).unwrap();
+    }
+
+    writeln!(out, "\n{} passed, {} failed, {} missing", count("PASS"), count("FAIL"), count("MISSING")).unwrap();
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::verify::*;
+
+    #[test]
+    fn test_parse_answers() {
+        // given
+        let _text = "# day 10\npart_one = \"1700\"\n\npart_two = 12401793332096\n";
+
+        // when
+        let _res = Answers::parse(_text, Path::new("answers.toml"));
+
+        // then
+        assert_eq!(_res.unwrap(), Answers { part_one: Some("1700".to_string()), part_two: Some("12401793332096".to_string()) });
+    }
+
+    #[test]
+    fn test_parse_answers_reports_line() {
+        // given
+        let _text = "part_one = \"1\"\npart_three = \"2\"\n";
+
+        // when
+        let _res = Answers::parse(_text, Path::new("answers.toml"));
+
+        // then
+        assert_eq!(
+            _res.unwrap_err().to_string(),
+            "answers.toml:2: invalid input, expected key part_one or part_two in 'part_three = \"2\"'"
+        );
+    }
+
+    #[test]
+    fn test_check() {
+        // given
+        let _solved = Status::Solved("42".to_string());
+        let _error = Status::Error("no solution".to_string());
+
+        // then
+        assert_eq!(check(&_solved, Some("42")), Verdict::Pass);
+        assert_eq!(check(&_solved, Some("41")), Verdict::Fail("expected 41, got 42".to_string()));
+        assert_eq!(check(&_solved, None), Verdict::Missing);
+        assert_eq!(check(&_error, Some("42")), Verdict::Fail("no solution".to_string()));
+    }
+
+    #[test]
+    fn test_verify_without_answers_file() {
+        // given
+        let _dir = std::env::temp_dir().join("aoc_2020_verify_without_answers");
+        let _source = InputSource::File(_dir.join("input.txt"));
+        let mut _result = PuzzleResult::new(1, "day_01".to_string());
+        _result.add_part(Part::One, Status::Solved("514579".to_string()), Default::default());
+
+        // when
+        let _res = verify(&[_result], &_source);
+
+        // then
+        let _expected = format!("day_01: {}: no answers file", _dir.join(ANSWERS_FILE_NAME).display());
+        assert_eq!(_res[0].verdict, Verdict::Fail(_expected));
+        assert!(has_failures(&_res));
+    }
+}
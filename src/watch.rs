@@ -0,0 +1,193 @@
+use std::{
+    fmt::Write,
+    fs,
+    path::PathBuf,
+    thread::sleep,
+    time::{Duration, SystemTime}
+};
+
+use crate::puzzles::{input::{self, InputSource}, solution::Part, Day};
+use crate::verify::{self, ANSWERS_FILE_NAME};
+
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+// modification times of the files a day is solved from, a file that
+// disappeared or can't be read has no time
+#[derive(Debug, Clone, PartialEq)]
+pub struct Snapshot(Vec<(PathBuf, Option<SystemTime>)>);
+
+impl Snapshot {
+    pub fn take(files: Vec<PathBuf>) -> Self {
+        Self(files.into_iter().map(|f| {
+            let modified = fs::metadata(&f).and_then(|m| m.modified()).ok();
+            (f, modified)
+        }).collect())
+    }
+
+    // files added, removed or modified since `previous`
+    pub fn changed_since(&self, previous: &Snapshot) -> Vec<PathBuf> {
+        let mut changed: Vec<PathBuf> = self.0.iter()
+            .filter(|entry| !previous.0.contains(entry))
+            .map(|(f, _)| f.clone())
+            .collect();
+        changed.extend(previous.0.iter().filter(|(f, _)| !self.0.iter().any(|(g, _)| f == g)).map(|(f, _)| f.clone()));
+        changed
+    }
+}
+
+// the input plus every example with its answers, listed again on every poll
+// so examples added while watching are picked up
+pub fn watched_files(day: &Day, source: &InputSource) -> Vec<PathBuf> {
+    let mut files = vec![source.path(day.name)];
+    for (_, each_example) in input::examples(day.name) {
+        files.push(each_example.with_file_name(ANSWERS_FILE_NAME));
+        files.push(each_example);
+    }
+    files
+}
+
+// one answer line, `value` is what gets compared between runs
+#[derive(Debug, Clone, PartialEq)]
+pub struct Line {
+    pub label: String,
+    pub part: Part,
+    pub value: String
+}
+
+// the input is solved as is, examples are checked against their answers
+pub fn solve(day: &Day, parts: &[Part], source: &InputSource, timeout: Option<Duration>) -> Vec<Line> {
+    let mut lines = vec![];
+
+    let result = (day.solve)(day.number, day.name, parts, source, timeout);
+    for each_part in &result.parts {
+        let value = match (each_part.status.answer(), each_part.status.message()) {
+            (Some(answer), _) => answer.to_string(),
+            (None, message) => format!("{}: {}", each_part.status, message.unwrap_or_default())
+        };
+        lines.push(Line { label: "input".to_string(), part: each_part.part, value });
+    }
+
+    for (name, path) in input::examples(day.name) {
+        // examples often only cover one part, all parts are solved when no answer is known
+        let answers = verify::Answers::load(&path.with_file_name(ANSWERS_FILE_NAME)).unwrap_or_default();
+        let example_parts: Vec<Part> = parts.iter().copied().filter(|p| answers.get(*p).is_some()).collect();
+        let example_parts = if example_parts.is_empty() { parts.to_vec() } else { example_parts };
+
+        let example_source = InputSource::File(path);
+        let result = (day.solve)(day.number, day.name, &example_parts, &example_source, timeout);
+        for each_check in verify::verify(&[result], &example_source) {
+            let detail = match &each_check.verdict {
+                verify::Verdict::Fail(reason) => reason.clone(),
+                _ => each_check.answer.clone().unwrap_or_default()
+            };
+            lines.push(Line {
+                label: format!("examples/{}", name),
+                part: each_check.part,
+                value: format!("{} {}", each_check.verdict, detail)
+            });
+        }
+    }
+
+    lines
+}
+
+// marks what changed compared to the previous run, nothing is marked on the first one
+pub fn render(lines: &[Line], previous: Option<&[Line]>) -> String {
+    let mut out = String::new();
+
+    for each_line in lines {
+        let change = match previous.map(|p| p.iter().find(|l| l.label == each_line.label && l.part == each_line.part)) {
+            None => String::new(),
+            Some(None) => "(new)".to_string(),
+            Some(Some(before)) if before.value == each_line.value => String::new(),
+            Some(Some(before)) => format!("(was {})", before.value)
+        };
+        writeln!(out, "{:<20} part {:<4} {} {}", each_line.label, each_line.part, each_line.value, change).unwrap();
+    }
+
+    out.lines().map(|l| l.trim_end().to_string() + "\n").collect()
+}
+
+// polls the watched files and solves the day again whenever one of them changes,
+// answers are printed with what they were before the change
+pub fn watch(day: &Day, parts: &[Part], source: &InputSource, timeout: Option<Duration>) -> ! {
+    let mut snapshot = Snapshot::take(watched_files(day, source));
+    let mut lines = solve(day, parts, source, timeout);
+    println!("watching {} files of {}, press Ctrl-C to stop\n", snapshot.0.len(), day.name);
+    print!("{}", render(&lines, None));
+
+    loop {
+        sleep(POLL_INTERVAL);
+
+        let current = Snapshot::take(watched_files(day, source));
+        let changed = current.changed_since(&snapshot);
+        if changed.is_empty() {
+            continue;
+        }
+
+        println!();
+        for each_file in &changed {
+            println!("changed {}", each_file.display());
+        }
+        let new_lines = solve(day, parts, source, timeout);
+        print!("{}", render(&new_lines, Some(&lines)));
+
+        snapshot = current;
+        lines = new_lines;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use crate::watch::*;
+
+    fn line(label: &str, part: Part, value: &str) -> Line {
+        Line { label: label.to_string(), part, value: value.to_string() }
+    }
+
+    #[test]
+    fn test_snapshot_changed_since() {
+        // given
+        let _file = env::temp_dir().join("aoc_watch_test_input.txt");
+        let _missing = env::temp_dir().join("aoc_watch_test_missing.txt");
+        fs::write(&_file, "1\n").unwrap();
+        let _before = Snapshot::take(vec![_file.clone(), _missing.clone()]);
+
+        // when
+        let _res_same = Snapshot::take(vec![_file.clone(), _missing.clone()]).changed_since(&_before);
+        fs::File::options().write(true).open(&_file).unwrap()
+            .set_modified(SystemTime::now() + Duration::from_secs(60)).unwrap();
+        let _res_modified = Snapshot::take(vec![_file.clone(), _missing.clone()]).changed_since(&_before);
+        let _res_removed = Snapshot::take(vec![_file.clone()]).changed_since(&_before);
+
+        // then
+        assert!(_res_same.is_empty());
+        assert_eq!(_res_modified, vec![_file.clone()]);
+        assert_eq!(_res_removed, vec![_file.clone(), _missing]);
+        fs::remove_file(&_file).unwrap();
+    }
+
+    #[test]
+    fn test_render_marks_changes() {
+        // given
+        let _before = vec![line("input", Part::One, "494475"), line("input", Part::Two, "267520550")];
+        let _after = vec![line("input", Part::One, "494476"), line("input", Part::Two, "267520550"), line("examples/example", Part::One, "PASS 514579")];
+
+        // when
+        let _res_first = render(&_before, None);
+        let _res_next = render(&_after, Some(&_before));
+
+        // then
+        assert_eq!(_res_first, "\
+input                part one 494475
+input                part two 267520550
+");
+        assert_eq!(_res_next, "\
+input                part one 494476 (was 494475)
+input                part two 267520550
+examples/example     part one PASS 514579 (new)
+");
+    }
+}
@@ -0,0 +1,29 @@
+use std::path::PathBuf;
+
+use aoc_2020::verify::{self, Answers, Verdict};
+use aoc_2020::{InputSource, Part, DAYS};
+
+// every example is an input.txt with the answers.toml it should produce, laid out
+// like the day itself, and solved the way `aoc verify --input` would solve it
+fn check_example(day_name: &str, example: &str) {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("src/puzzles")
+        .join(day_name)
+        .join("examples")
+        .join(example);
+
+    // examples often only cover one part
+    let answers = Answers::load(&dir.join(verify::ANSWERS_FILE_NAME)).unwrap();
+    let parts: Vec<Part> = Part::ALL.iter().copied().filter(|p| answers.get(*p).is_some()).collect();
+    assert!(!parts.is_empty(), "{} has no answers", dir.display());
+
+    let day = DAYS.iter().find(|d| d.name == day_name).unwrap();
+    let source = InputSource::File(dir.join("input.txt"));
+    let result = (day.solve)(day.number, day.name, &parts, &source, None);
+
+    for check in verify::verify(&[result], &source) {
+        assert_eq!(check.verdict, Verdict::Pass, "{} example {} part {}", day_name, example, check.part);
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/examples.rs"));